@@ -0,0 +1,119 @@
+-- Default scripts of popusk. `popusk config init` writes this file to ~/.config/popusk/scripts.lua,
+-- so it can be changed. Types of arguments are described in docs/lua_script.md.
+
+local function progress_string(progress)
+    return string.format(
+        "%d/%d (%d%%)",
+        progress.passed,
+        progress.ceiling,
+        progress.passed * 100 // progress.ceiling
+    )
+end
+
+local function tags_string(tags)
+    if #tags == 0 then
+        return "<no tags>"
+    end
+    return table.concat(tags, " ")
+end
+
+-- Progress of documents and finished documents of sections.
+local function status_string(libentity)
+    if libentity.etype == "document" then
+        return progress_string(libentity.progress)
+    elseif libentity.etype == "section" then
+        local aggregate = libentity.aggregate_progress
+        return string.format("%d/%d documents", aggregate.finished_documents, aggregate.documents)
+    end
+    return ""
+end
+
+-- Pads the string to the width or cuts it marking the cut by "~".
+local function fit(str, width)
+    local len = utf8.len(str)
+    if len <= width then
+        return str .. string.rep(" ", width - len)
+    end
+    return str:sub(1, utf8.offset(str, width) - 1) .. "~"
+end
+
+function look_output(libentity, context)
+    local lines = {
+        libentity.name,
+        string.rep("=", math.min(utf8.len(libentity.name), context.tshape_w)),
+        "path:     " .. libentity.path,
+        "id:       " .. libentity.id,
+        "type:     " .. libentity.etype,
+        "tags:     " .. tags_string(libentity.tags),
+    }
+
+    if libentity.etype ~= "regular" then
+        table.insert(lines, "progress: " .. status_string(libentity))
+    end
+
+    local bibliography = libentity.bibliography
+    if bibliography ~= nil then
+        if #bibliography.authors > 0 then
+            table.insert(lines, "authors:  " .. table.concat(bibliography.authors, ", "))
+        end
+        if bibliography.year ~= nil then
+            table.insert(lines, "year:     " .. bibliography.year)
+        end
+    end
+
+    if #libentity.collections > 0 then
+        table.insert(lines, "collections: " .. table.concat(libentity.collections, ", "))
+    end
+    for _, link in ipairs(libentity.links) do
+        table.insert(lines, string.format("%s -> %s", link.relation, link.name))
+    end
+    for _, backlink in ipairs(libentity.backlinks) do
+        table.insert(lines, string.format("%s <- %s", backlink.relation, backlink.name))
+    end
+
+    if libentity.description ~= nil then
+        table.insert(lines, "")
+        for _, line in ipairs(libentity.description_lines) do
+            table.insert(lines, line)
+        end
+    end
+
+    return table.concat(lines, "\n")
+end
+
+function list_output_narrow(libentities, context)
+    if #libentities == 0 then
+        return "<no library entities>"
+    end
+
+    local status_width = 24
+    local name_width = math.max(context.tshape_w - status_width - 1, 10)
+    local lines = {}
+    for _, libentity in ipairs(libentities) do
+        table.insert(lines, fit(libentity.name, name_width) .. " " .. status_string(libentity))
+    end
+
+    return table.concat(lines, "\n")
+end
+
+function list_output_wide(libentities, context)
+    if #libentities == 0 then
+        return "<no library entities>"
+    end
+
+    local blocks = {}
+    for _, libentity in ipairs(libentities) do
+        local block = {
+            string.format("[%s] %s", libentity.id, libentity.name),
+            "    path: " .. libentity.path,
+            "    type: " .. libentity.etype,
+            "    tags: " .. tags_string(libentity.tags),
+        }
+        if libentity.etype ~= "regular" then
+            table.insert(block, "    progress: " .. status_string(libentity))
+        end
+        table.insert(blocks, table.concat(block, "\n"))
+    end
+
+    return table.concat(blocks, "\n\n")
+end
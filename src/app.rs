@@ -1,14 +1,16 @@
 use crate::config::{read_config, Config, ConfigError};
 use crate::library::{Library, LibraryError};
+use crate::lua_library::LuaLibrary;
 use crate::scripts::{open_scripts, Scripts, ScriptsError};
 use crate::storage::{Storage, StorageError};
 
+use std::cell::OnceCell;
 use std::io::Error as IoError;
 use std::path::PathBuf;
 
 use thiserror::Error;
 
-const WORKING_DIR: &str = ".popusk";
+pub const WORKING_DIR: &str = ".popusk";
 
 #[derive(Debug, Error)]
 pub enum AppError {
@@ -24,19 +26,20 @@ pub enum AppError {
     IO(#[from] IoError),
 }
 
-/// Contains all information about application state - storage, config, etc.
+/// Contains all information about application state - storage, config, etc. Config and scripts
+/// are loaded at the first use, so commands that don't use them work with broken ones.
 pub struct App {
     library: Library,
-    config: Config,
-    scripts: Scripts,
+    config: OnceCell<Config>,
+    scripts: OnceCell<Scripts>,
 }
 
 impl App {
     pub fn open() -> Result<Self, AppError> {
         Ok(App {
             library: Library::new(Storage::open_with_working_dir(&PathBuf::from(WORKING_DIR))?),
-            config: read_config()?,
-            scripts: open_scripts()?,
+            config: OnceCell::new(),
+            scripts: OnceCell::new(),
         })
     }
 
@@ -47,8 +50,8 @@ impl App {
 
         Ok(App {
             library: Library::new(Storage::create_with_working_dir(&working_dir_path)?),
-            config: read_config()?,
-            scripts: open_scripts()?,
+            config: OnceCell::new(),
+            scripts: OnceCell::new(),
         })
     }
 
@@ -60,11 +63,25 @@ impl App {
         &mut self.library
     }
 
-    pub fn config(&self) -> &Config {
-        &self.config
+    /// Reads the config at the first call.
+    pub fn config(&self) -> Result<&Config, AppError> {
+        if let Some(config) = self.config.get() {
+            return Ok(config);
+        }
+
+        let config = read_config()?;
+        Ok(self.config.get_or_init(|| config))
     }
 
-    pub fn scripts(&self) -> &Scripts {
-        &self.scripts
+    /// Opens the scripts at the first call.
+    pub fn scripts(&self) -> Result<&Scripts, AppError> {
+        if let Some(scripts) = self.scripts.get() {
+            return Ok(scripts);
+        }
+
+        let config = self.config()?;
+        let scripts = open_scripts(config)?;
+        scripts.set_library(LuaLibrary::new(config.clone()));
+        Ok(self.scripts.get_or_init(|| scripts))
     }
 }
@@ -0,0 +1,185 @@
+//! Here is bibliographic data of library entities: authors, year, publisher, etc. All fields are
+//! optional, library entity without any of them has no bibliography at all.
+
+use crate::error_ext::ComResult;
+
+use serde_derive::{Deserialize, Serialize};
+
+/// Authors are separated by this string when they're given as one string.
+pub const AUTHORS_SEPARATOR: &str = ";";
+
+/// Names of the fields in the order they're shown.
+pub const BIBLIOGRAPHY_FIELDS: [&str; 9] = [
+    "authors",
+    "year",
+    "publisher",
+    "isbn",
+    "doi",
+    "language",
+    "edition",
+    "series",
+    "series_index",
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Bibliography {
+    #[serde(default)]
+    pub authors: Vec<String>,
+    pub year: Option<i32>,
+    pub publisher: Option<String>,
+    pub isbn: Option<String>,
+    pub doi: Option<String>,
+    pub language: Option<String>,
+    pub edition: Option<String>,
+    pub series: Option<String>,
+    /// Position of the library entity in the series. It isn't always integer, e.g. `1.5`.
+    pub series_index: Option<f64>,
+}
+
+fn non_empty_trimmed(string: &str) -> Option<String> {
+    match string.trim() {
+        "" => None,
+        trimmed => Some(trimmed.to_string()),
+    }
+}
+
+/// ISBN may contain hyphens and spaces, but there must be 10 or 13 digits (the last digit of
+/// ISBN-10 may be `X`).
+fn isbn_is_valid(isbn: &str) -> bool {
+    let symbols = isbn
+        .chars()
+        .filter(|symbol| *symbol != '-' && !symbol.is_whitespace())
+        .collect::<Vec<_>>();
+
+    match symbols.split_last() {
+        Some((last, rest)) if symbols.len() == 10 => {
+            rest.iter().all(char::is_ascii_digit) && (last.is_ascii_digit() || *last == 'X')
+        }
+        Some(_) if symbols.len() == 13 => symbols.iter().all(char::is_ascii_digit),
+        _ => false,
+    }
+}
+
+impl Bibliography {
+    pub fn is_empty(&self) -> bool {
+        *self == Bibliography::default()
+    }
+
+    /// Returns value of the field as a string. Authors are joined by `AUTHORS_SEPARATOR`.
+    pub fn field(&self, field: &str) -> ComResult<Option<String>> {
+        Ok(match field {
+            "authors" if self.authors.is_empty() => None,
+            "authors" => Some(self.authors.join(&format!("{} ", AUTHORS_SEPARATOR))),
+            "year" => self.year.map(|year| year.to_string()),
+            "publisher" => self.publisher.clone(),
+            "isbn" => self.isbn.clone(),
+            "doi" => self.doi.clone(),
+            "language" => self.language.clone(),
+            "edition" => self.edition.clone(),
+            "series" => self.series.clone(),
+            "series_index" => self.series_index.map(|index| index.to_string()),
+            _ => return Err(format!("unknown bibliography field '{}'", field).into()),
+        })
+    }
+
+    /// Sets the field from a string. Empty value unsets the field. Authors are separated by
+    /// `AUTHORS_SEPARATOR`.
+    pub fn set_field(&mut self, field: &str, value: &str) -> ComResult<()> {
+        let value = non_empty_trimmed(value);
+
+        match field {
+            "authors" => {
+                self.authors = value
+                    .unwrap_or_default()
+                    .split(AUTHORS_SEPARATOR)
+                    .filter_map(non_empty_trimmed)
+                    .collect()
+            }
+            "year" => {
+                self.year = match value {
+                    Some(year) => Some(
+                        year.parse::<i32>()
+                            .map_err(|_| format!("invalid year '{}'", year))?,
+                    ),
+                    None => None,
+                }
+            }
+            "publisher" => self.publisher = value,
+            "isbn" => self.isbn = value,
+            "doi" => self.doi = value,
+            "language" => self.language = value,
+            "edition" => self.edition = value,
+            "series" => self.series = value,
+            "series_index" => {
+                self.series_index = match value {
+                    Some(index) => Some(
+                        index
+                            .parse::<f64>()
+                            .map_err(|_| format!("invalid series index '{}'", index))?,
+                    ),
+                    None => None,
+                }
+            }
+            _ => return Err(format!("unknown bibliography field '{}'", field).into()),
+        }
+
+        self.check()
+    }
+
+    /// Checks values of the fields. Trimming and removing of empty values is done by
+    /// `Self::normalized`.
+    pub fn check(&self) -> ComResult<()> {
+        if let Some(isbn) = &self.isbn {
+            if !isbn_is_valid(isbn) {
+                return Err(format!("invalid ISBN '{}'", isbn).into());
+            }
+        }
+
+        if let Some(series_index) = self.series_index {
+            if !series_index.is_finite() || series_index < 0.0 {
+                return Err(format!("invalid series index '{}'", series_index).into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Trims the fields and removes empty ones. Returns `None` if no fields are left.
+    pub fn normalized(self) -> ComResult<Option<Bibliography>> {
+        let normalize = |field: Option<String>| field.as_deref().and_then(non_empty_trimmed);
+
+        let bibliography = Bibliography {
+            authors: self
+                .authors
+                .iter()
+                .filter_map(|author| non_empty_trimmed(author))
+                .collect(),
+            year: self.year,
+            publisher: normalize(self.publisher),
+            isbn: normalize(self.isbn),
+            doi: normalize(self.doi),
+            language: normalize(self.language),
+            edition: normalize(self.edition),
+            series: normalize(self.series),
+            series_index: self.series_index,
+        };
+        bibliography.check()?;
+
+        if bibliography.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(bibliography))
+        }
+    }
+
+    /// Returns `true` if any of the fields contains the query. The query must be in lower case.
+    pub fn matches(&self, lowercase_query: &str) -> bool {
+        BIBLIOGRAPHY_FIELDS.iter().any(|field| {
+            self.field(field)
+                .ok()
+                .flatten()
+                .map(|value| value.to_lowercase().contains(lowercase_query))
+                .unwrap_or(false)
+        })
+    }
+}
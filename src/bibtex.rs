@@ -0,0 +1,521 @@
+//! Here is reading and writing of BibTeX entries. Only the subset needed for exchanging
+//! bibliographies is supported: `@string` macros aren't expanded and TeX commands in values are
+//! kept as is except escaped symbols and braces.
+
+use std::fmt::{Display, Formatter};
+
+use thiserror::Error;
+
+/// Symbols that must be escaped in BibTeX values.
+const SPECIAL_SYMBOLS: [char; 5] = ['&', '%', '$', '#', '_'];
+/// Words that aren't used in citation keys.
+const STOP_WORDS: [&str; 7] = ["a", "an", "the", "on", "of", "in", "and"];
+/// Authors in the `author` field are separated by this word.
+pub const BIBTEX_AUTHORS_SEPARATOR: &str = " and ";
+
+#[derive(Debug, Error)]
+pub enum BibtexError {
+    #[error("unexpected end of the file in the entry at line {line}")]
+    UnexpectedEnd { line: usize },
+    #[error("expected '{expected}' at line {line}")]
+    Expected { expected: char, line: usize },
+    #[error("empty field name at line {line}")]
+    EmptyFieldName { line: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BibtexEntry {
+    /// Type of the entry in lower case, e.g. `book`.
+    pub entry_type: String,
+    pub key: String,
+    /// Names of the fields are in lower case.
+    pub fields: Vec<(String, String)>,
+}
+
+impl BibtexEntry {
+    pub fn new(entry_type: String, key: String) -> Self {
+        BibtexEntry {
+            entry_type,
+            key,
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field_name, _)| field_name == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Empty values are skipped.
+    pub fn push_field(&mut self, name: &str, value: String) {
+        if !value.trim().is_empty() {
+            self.fields.push((name.to_string(), value));
+        }
+    }
+}
+
+fn escape_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for symbol in value.chars() {
+        match symbol {
+            // Unbalanced braces break the entry, so they're dropped.
+            '{' | '}' => (),
+            symbol if SPECIAL_SYMBOLS.contains(&symbol) => {
+                escaped.push('\\');
+                escaped.push(symbol);
+            }
+            symbol => escaped.push(symbol),
+        }
+    }
+
+    escaped
+}
+
+impl Display for BibtexEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "@{}{{{},", self.entry_type, self.key)?;
+        for (name, value) in &self.fields {
+            writeln!(f, "  {} = {{{}}},", name, escape_value(value))?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// Keeps only ASCII letters and digits in lower case.
+fn key_part(string: &str) -> String {
+    string
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|symbol| symbol.to_ascii_lowercase())
+        .collect()
+}
+
+/// Returns the last name of the author given as `First Last` or `Last, First`.
+fn last_name(author: &str) -> &str {
+    match author.split_once(',') {
+        Some((last_name, _)) => last_name.trim(),
+        None => author.split_whitespace().last().unwrap_or(""),
+    }
+}
+
+/// Makes citation key like `knuth1984texbook` from the last name of the first author, the year
+/// and the first significant word of the title. The same data always gives the same key.
+pub fn citation_key(authors: &[String], year: Option<i32>, title: &str) -> String {
+    let author = authors
+        .first()
+        .map(|author| key_part(last_name(author)))
+        .unwrap_or_default();
+    let year = year.map(|year| year.to_string()).unwrap_or_default();
+    let word = title
+        .split_whitespace()
+        .map(key_part)
+        .find(|word| !word.is_empty() && !STOP_WORDS.contains(&word.as_str()))
+        .unwrap_or_default();
+
+    let key = format!("{}{}{}", author, year, word);
+    if key.is_empty() {
+        "entry".to_string()
+    } else {
+        key
+    }
+}
+
+/// Removes braces and escaping backslashes, collapses whitespaces.
+fn clean_value(value: &str) -> String {
+    let mut cleaned = String::with_capacity(value.len());
+    let mut symbols = value.chars().peekable();
+
+    while let Some(symbol) = symbols.next() {
+        match symbol {
+            '{' | '}' => (),
+            '\\' if symbols
+                .peek()
+                .map(|next| SPECIAL_SYMBOLS.contains(next) || *next == '{' || *next == '}')
+                .unwrap_or(false) =>
+            {
+                cleaned.push(symbols.next().unwrap());
+            }
+            symbol => cleaned.push(symbol),
+        }
+    }
+
+    cleaned.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+struct Parser {
+    symbols: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn line(&self) -> usize {
+        self.symbols[..self.position.min(self.symbols.len())]
+            .iter()
+            .filter(|symbol| **symbol == '\n')
+            .count()
+            + 1
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.symbols.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Result<char, BibtexError> {
+        let symbol = self
+            .peek()
+            .ok_or(BibtexError::UnexpectedEnd { line: self.line() })?;
+        self.position += 1;
+
+        Ok(symbol)
+    }
+
+    fn skip_whitespaces(&mut self) {
+        while self.peek().map(char::is_whitespace).unwrap_or(false) {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), BibtexError> {
+        self.skip_whitespaces();
+        if self.next()? != expected {
+            return Err(BibtexError::Expected {
+                expected,
+                line: self.line(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Reads symbols until one of the stop symbols or a whitespace.
+    fn read_word(&mut self, stop_symbols: &[char]) -> String {
+        let mut word = String::new();
+        while let Some(symbol) = self.peek() {
+            if symbol.is_whitespace() || stop_symbols.contains(&symbol) {
+                break;
+            }
+            word.push(symbol);
+            self.position += 1;
+        }
+
+        word
+    }
+
+    /// Reads content of the group up to the closing brace. The opening one must be already read.
+    fn read_braced(&mut self) -> Result<String, BibtexError> {
+        let mut content = String::new();
+        let mut depth = 0;
+
+        loop {
+            match self.next()? {
+                '\\' => {
+                    content.push('\\');
+                    content.push(self.next()?);
+                }
+                '{' => {
+                    depth += 1;
+                    content.push('{');
+                }
+                '}' if depth == 0 => return Ok(content),
+                '}' => {
+                    depth -= 1;
+                    content.push('}');
+                }
+                symbol => content.push(symbol),
+            }
+        }
+    }
+
+    /// Reads content of the quoted string. The opening quote must be already read. Quotes inside
+    /// braces don't close the string.
+    fn read_quoted(&mut self) -> Result<String, BibtexError> {
+        let mut content = String::new();
+        let mut depth = 0;
+
+        loop {
+            match self.next()? {
+                '\\' => {
+                    content.push('\\');
+                    content.push(self.next()?);
+                }
+                '"' if depth == 0 => return Ok(content),
+                '{' => {
+                    depth += 1;
+                    content.push('{');
+                }
+                '}' => {
+                    depth -= 1;
+                    content.push('}');
+                }
+                symbol => content.push(symbol),
+            }
+        }
+    }
+
+    /// Reads the value that may be concatenated from several parts by `#`.
+    fn read_value(&mut self, closing: char) -> Result<String, BibtexError> {
+        let mut value = String::new();
+
+        loop {
+            self.skip_whitespaces();
+            match self.next()? {
+                '{' => value.push_str(&self.read_braced()?),
+                '"' => value.push_str(&self.read_quoted()?),
+                _ => {
+                    // Numbers and macros. Macros aren't expanded.
+                    self.position -= 1;
+                    value.push_str(&self.read_word(&[',', '#', closing]));
+                }
+            }
+
+            self.skip_whitespaces();
+            if self.peek() == Some('#') {
+                self.position += 1;
+            } else {
+                return Ok(clean_value(&value));
+            }
+        }
+    }
+
+    /// Reads the entry after `@type`. Returns `None` for `@comment`, `@preamble` and `@string`.
+    fn read_entry(&mut self, entry_type: String) -> Result<Option<BibtexEntry>, BibtexError> {
+        self.skip_whitespaces();
+        let closing = match self.next()? {
+            '{' => '}',
+            '(' => ')',
+            _ => {
+                return Err(BibtexError::Expected {
+                    expected: '{',
+                    line: self.line(),
+                })
+            }
+        };
+
+        if ["comment", "preamble", "string"].contains(&entry_type.as_str()) {
+            if closing == '}' {
+                self.read_braced()?;
+            } else {
+                while self.next()? != ')' {}
+            }
+            return Ok(None);
+        }
+
+        self.skip_whitespaces();
+        let key = self.read_word(&[',', closing]);
+        let mut entry = BibtexEntry::new(entry_type, key);
+
+        loop {
+            self.skip_whitespaces();
+            match self.next()? {
+                ',' => (),
+                symbol if symbol == closing => return Ok(Some(entry)),
+                _ => {
+                    return Err(BibtexError::Expected {
+                        expected: closing,
+                        line: self.line(),
+                    })
+                }
+            }
+
+            self.skip_whitespaces();
+            // Trailing comma before the end of the entry.
+            if self.peek() == Some(closing) {
+                continue;
+            }
+
+            let name = self.read_word(&['=', ',', closing]).to_lowercase();
+            if name.is_empty() {
+                return Err(BibtexError::EmptyFieldName { line: self.line() });
+            }
+            self.expect('=')?;
+            let value = self.read_value(closing)?;
+
+            entry.fields.push((name, value));
+        }
+    }
+}
+
+/// Parses entries of the BibTeX file. Text outside of entries is ignored as a comment.
+pub fn parse_bibtex(content: &str) -> Result<Vec<BibtexEntry>, BibtexError> {
+    let mut parser = Parser {
+        symbols: content.chars().collect(),
+        position: 0,
+    };
+    let mut entries = Vec::new();
+
+    while let Some(symbol) = parser.peek() {
+        parser.position += 1;
+        if symbol != '@' {
+            continue;
+        }
+
+        let entry_type = parser.read_word(&['{', '(']).to_lowercase();
+        if let Some(entry) = parser.read_entry(entry_type)? {
+            entries.push(entry);
+        }
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_single(content: &str) -> BibtexEntry {
+        let mut entries = parse_bibtex(content).unwrap();
+        assert_eq!(entries.len(), 1);
+        entries.remove(0)
+    }
+
+    #[test]
+    fn parses_fields_of_entry() {
+        let entry = parse_single(
+            "@Book{knuth1984texbook,\n  Title = {The {\\TeX}book},\n  year = 1984,\n}",
+        );
+
+        assert_eq!(entry.entry_type, "book");
+        assert_eq!(entry.key, "knuth1984texbook");
+        assert_eq!(entry.field("title"), Some("The \\TeXbook"));
+        assert_eq!(entry.field("year"), Some("1984"));
+    }
+
+    #[test]
+    fn reads_nested_braces() {
+        let entry = parse_single("@article{key, title = {On {the {Nested}} Braces}}");
+
+        assert_eq!(entry.field("title"), Some("On the Nested Braces"));
+    }
+
+    #[test]
+    fn reads_escaped_braces_and_symbols() {
+        let entry = parse_single("@misc{key, note = {50\\% of \\{braces\\} \\& more}}");
+
+        assert_eq!(entry.field("note"), Some("50% of {braces} & more"));
+    }
+
+    #[test]
+    fn quotes_inside_braces_do_not_close_value() {
+        let entry = parse_single("@misc{key, title = \"A {\"quoted\"} word\"}");
+
+        assert_eq!(entry.field("title"), Some("A \"quoted\" word"));
+    }
+
+    #[test]
+    fn reads_quotes_inside_braced_value() {
+        let entry = parse_single("@misc{key, title = {A \"quoted\" word}}");
+
+        assert_eq!(entry.field("title"), Some("A \"quoted\" word"));
+    }
+
+    #[test]
+    fn concatenates_parts_and_keeps_macros() {
+        let entry = parse_single("@misc{key, note = \"First\" # { second} # jan}");
+
+        assert_eq!(entry.field("note"), Some("First secondjan"));
+    }
+
+    #[test]
+    fn reads_entry_in_parentheses() {
+        let entry = parse_single("@book(key, title = {Title})");
+
+        assert_eq!(entry.field("title"), Some("Title"));
+    }
+
+    #[test]
+    fn skips_comments_preambles_and_strings() {
+        let entries = parse_bibtex(
+            "Text outside of entries.\n\
+             @comment{ignored {nested} @book{fake, title = {Fake}} }\n\
+             @preamble{\"\\newcommand{\\noop}[1]{}\"}\n\
+             @string(jan = \"January\")\n\
+             @book{real, title = {Real}}",
+        )
+        .unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key, "real");
+    }
+
+    #[test]
+    fn reports_line_of_missing_equals_sign() {
+        let result = parse_bibtex("@book{key,\n  title = {A},\n  author {B}\n}");
+
+        assert!(matches!(
+            result,
+            Err(BibtexError::Expected {
+                expected: '=',
+                line: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn reports_line_of_unexpected_end() {
+        let result = parse_bibtex("@book{key,\n  title = {unclosed");
+
+        assert!(matches!(
+            result,
+            Err(BibtexError::UnexpectedEnd { line: 2 })
+        ));
+    }
+
+    #[test]
+    fn reports_line_of_empty_field_name() {
+        let result = parse_bibtex("@book{key,\n\n  = {value}}");
+
+        assert!(matches!(
+            result,
+            Err(BibtexError::EmptyFieldName { line: 3 })
+        ));
+    }
+
+    #[test]
+    fn escaped_value_is_parsed_back() {
+        let mut entry = BibtexEntry::new("book".to_string(), "key".to_string());
+        entry.push_field("title", "C# & C++: 100% of $ and _".to_string());
+        entry.push_field("note", "Unbalanced { brace".to_string());
+
+        let parsed = parse_single(&entry.to_string());
+
+        assert_eq!(parsed.field("title"), Some("C# & C++: 100% of $ and _"));
+        assert_eq!(parsed.field("note"), Some("Unbalanced brace"));
+    }
+
+    #[test]
+    fn escapes_special_symbols_and_drops_braces() {
+        assert_eq!(escape_value("a_b {c} 5%"), "a\\_b c 5\\%");
+    }
+
+    #[test]
+    fn citation_key_is_made_of_author_year_and_word() {
+        let authors = vec!["Donald E. Knuth".to_string()];
+        assert_eq!(
+            citation_key(&authors, Some(1984), "The TeXbook"),
+            "knuth1984texbook"
+        );
+
+        let authors = vec!["Knuth, Donald".to_string(), "Someone Else".to_string()];
+        assert_eq!(
+            citation_key(&authors, Some(1984), "The TeXbook"),
+            "knuth1984texbook"
+        );
+    }
+
+    #[test]
+    fn citation_key_skips_missing_parts() {
+        assert_eq!(citation_key(&[], None, "On the Art"), "art");
+        assert_eq!(citation_key(&[], None, ""), "entry");
+    }
+
+    #[test]
+    fn citation_key_is_parsed_back() {
+        let key = citation_key(&["Müller, Jörg".to_string()], Some(2000), "Über Alles");
+        let entry = BibtexEntry::new("book".to_string(), key.clone());
+
+        assert_eq!(parse_single(&entry.to_string()).key, key);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::comps_interaction::TAG_SEPARATOR;
 use crate::entity_base::{EntityBase, EntityType, Tag};
 use crate::error_ext::ComResult;
 use crate::progress::Progress;
@@ -17,10 +18,18 @@ pub fn entitytype_to_string(etype: EntityType) -> String {
     }
 }
 
+/// Separators of hierarchical tags are normalized: `/math//algebra/` becomes `math/algebra`.
 pub fn parse_string_to_tags(stringifed_tags: &str) -> ComResult<Vec<Tag>> {
     Ok(stringifed_tags
         .split(' ')
-        .map(|tag| tag.trim().to_string())
+        .map(|tag| {
+            tag.split(TAG_SEPARATOR)
+                .map(|segment| segment.trim())
+                .filter(|segment| !segment.is_empty())
+                .collect::<Vec<_>>()
+                .join(&TAG_SEPARATOR.to_string())
+        })
+        .filter(|tag| !tag.is_empty())
         .collect())
 }
 
@@ -32,7 +41,7 @@ pub fn entitybase_to_oneline_string(entitybase: &EntityBase) -> String {
     )
 }
 
-fn tags_to_string(tags: &[Tag]) -> String {
+pub fn tags_to_string(tags: &[Tag]) -> String {
     if tags.len() == 0 {
         return "<no tags>".to_string();
     }
@@ -1,8 +1,42 @@
 //! Here is defined how components (progress, bases, etc) interacts without any context (such as
 //! `Storage`, `Config`, etc).
 
-use crate::entity_base::EntityType;
+use crate::entity_base::{EntityType, Tag};
+
+/// Separates segments of hierarchical tags: `math/algebra` is a descendant of `math`.
+pub const TAG_SEPARATOR: char = '/';
 
 pub fn libentity_has_progress(etype: EntityType) -> bool {
     etype == EntityType::Document
 }
+
+/// Tags are separated by spaces, so they can't contain whitespaces. Segments of hierarchical tags
+/// mustn't be empty.
+pub fn tag_is_valid(tag: &str) -> bool {
+    !tag.contains(char::is_whitespace)
+        && tag.split(TAG_SEPARATOR).all(|segment| !segment.is_empty())
+}
+
+/// Returns segments of the hierarchical tag: `math/algebra` gives `["math", "algebra"]`.
+pub fn tag_segments(tag: &str) -> Vec<&str> {
+    tag.split(TAG_SEPARATOR).collect()
+}
+
+/// Returns `true` if the tag is `filter` itself or its descendant. So, filtering by `math` matches
+/// `math/algebra` but not `mathematics`.
+pub fn tag_matches(tag: &str, filter: &str) -> bool {
+    match tag.strip_prefix(filter) {
+        Some(rest) => rest.is_empty() || rest.starts_with(TAG_SEPARATOR),
+        None => false,
+    }
+}
+
+/// Moves the tag from the `old_ancestor` subtree to the `new_ancestor` one. Returns `None` if the
+/// tag isn't in the `old_ancestor` subtree.
+pub fn replace_tag_ancestor(tag: &str, old_ancestor: &str, new_ancestor: &str) -> Option<Tag> {
+    if tag_matches(tag, old_ancestor) {
+        Some(format!("{}{}", new_ancestor, &tag[old_ancestor.len()..]))
+    } else {
+        None
+    }
+}
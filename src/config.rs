@@ -7,12 +7,13 @@ use serde_derive::{Deserialize, Serialize};
 use thiserror::Error;
 use toml::{de::Error as TomlDEError, from_str as toml_from_str};
 
+/// Config used if the user's one doesn't exist.
+pub const DEFAULT_CONFIG: &str = include_str!("../defaults/popusk.toml");
+
 #[derive(Debug, Error)]
 pub enum ConfigError {
     #[error("global conf: {0}")]
     GlobalConfError(#[from] GlobalConfError),
-    #[error("couldn't find config in the path '{path}'")]
-    ConfigDoesNotExist { path: PathBuf },
     #[error("I/O error occured: {0}")]
     IO(#[from] IoError),
     #[error("couldn't parse syntax of config file (toml): {0}")]
@@ -23,6 +24,12 @@ pub enum ConfigError {
 pub struct Config {
     document_extension: Box<[String]>,
     viewer: Box<[String]>,
+    /// Allows scripts to change the library through mutating functions of the `popusk` global.
+    #[serde(default)]
+    scripts_modify_library: bool,
+    /// Allows loading the scripts file of the library in the current directory.
+    #[serde(default)]
+    library_scripts: bool,
 }
 
 impl Config {
@@ -33,20 +40,27 @@ impl Config {
     pub fn viewer(&self) -> &[String] {
         &self.viewer
     }
+
+    pub fn scripts_modify_library(&self) -> bool {
+        self.scripts_modify_library
+    }
+
+    pub fn library_scripts(&self) -> bool {
+        self.library_scripts
+    }
 }
 
-fn configfile() -> Result<PathBuf, ConfigError> {
+pub fn configfile() -> Result<PathBuf, ConfigError> {
     Ok(configdir()?.join(format!("{}.toml", env!("CARGO_PKG_NAME"))))
 }
 
+/// Reads the user's config or, if it doesn't exist, the default one.
 pub fn read_config() -> Result<Config, ConfigError> {
     let configfile = configfile()?;
 
     let config_strcontent = match std::fs::read_to_string(&configfile) {
         Ok(c) => c,
-        Err(io_error) if io_error.kind() == IoErrorKind::NotFound => {
-            return Err(ConfigError::ConfigDoesNotExist { path: configfile });
-        }
+        Err(io_error) if io_error.kind() == IoErrorKind::NotFound => DEFAULT_CONFIG.to_string(),
         Err(io_error) => return Err(io_error.into()),
     };
 
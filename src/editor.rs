@@ -0,0 +1,78 @@
+//! Here is editing of text in the external editor. The editor is taken from `$VISUAL` or
+//! `$EDITOR` environment variables.
+
+use std::env::var as env_var;
+use std::io::Error as IoError;
+use std::path::PathBuf;
+use std::process::Command;
+
+use thiserror::Error;
+
+const EDITOR_VARIABLES: [&str; 2] = ["VISUAL", "EDITOR"];
+const DEFAULT_EDITOR: &str = "vi";
+
+#[derive(Debug, Error)]
+pub enum EditorError {
+    #[error("editor command is empty")]
+    EmptyEditorCommand,
+    #[error("can't spawn editor process '{editor}': {io_error}")]
+    CouldNotSpawnEditor { editor: String, io_error: IoError },
+    #[error("editor exited unsuccesfully")]
+    EditorExitedUnsuccessfully,
+    #[error("an I/O error occured: {0}")]
+    IO(#[from] IoError),
+}
+
+/// Returns the editor and its arguments. For example, `$EDITOR` may be `code --wait`.
+fn editor_command() -> Vec<String> {
+    let editor = EDITOR_VARIABLES
+        .iter()
+        .filter_map(|variable| env_var(variable).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string());
+
+    editor
+        .split_whitespace()
+        .map(|arg| arg.to_string())
+        .collect()
+}
+
+fn temporary_file(name: &str, extension: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "{}-{}-{}.{}",
+        env!("CARGO_PKG_NAME"),
+        name,
+        std::process::id(),
+        extension
+    ))
+}
+
+/// Writes the content to a temporary file, opens it in the editor and returns the content of the
+/// file after the editor exits. `extension` of the file lets the editor highlight syntax.
+pub fn edit_text(content: &str, name: &str, extension: &str) -> Result<String, EditorError> {
+    let editor_command = editor_command();
+    let (editor, editor_args) = match editor_command.split_first() {
+        Some(editor_command) => editor_command,
+        None => return Err(EditorError::EmptyEditorCommand),
+    };
+
+    let file_path = temporary_file(name, extension);
+    std::fs::write(&file_path, content)?;
+
+    let exit_status = Command::new(editor)
+        .args(editor_args)
+        .arg(&file_path)
+        .spawn()
+        .and_then(|mut child| child.wait());
+    let edited_content = std::fs::read_to_string(&file_path);
+    std::fs::remove_file(&file_path)?;
+
+    match exit_status {
+        Ok(exit_status) if exit_status.success() => Ok(edited_content?),
+        Ok(_) => Err(EditorError::EditorExitedUnsuccessfully),
+        Err(io_error) => Err(EditorError::CouldNotSpawnEditor {
+            editor: editor.clone(),
+            io_error,
+        }),
+    }
+}
@@ -0,0 +1,111 @@
+//! Here is the selector of library entities that commands take instead of paths or IDs. The
+//! selector is one of the following (the first matching variant is taken):
+//! 1. `#<id>`, e.g. `#12`.
+//! 2. Path of the library entity.
+//! 3. ID without `#`, if there's library entity with the ID. It's left for compatibility with
+//!    commands that took IDs.
+//! 4. Name of the library entity or a prefix of it (case insensitive). The name must be unique.
+
+use crate::id::ID;
+use crate::library::{Library, LibraryError};
+
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+const ID_PREFIX: char = '#';
+
+#[derive(Debug, Error)]
+pub enum EntitySelectorError {
+    #[error("library error: {0}")]
+    Library(#[from] LibraryError),
+    #[error("couldn't parse ID from '{0}'")]
+    InvalidID(String),
+    #[error("couldn't find library entity matching '{0}'")]
+    NotFound(String),
+    #[error("'{selector}' matches several library entities: {}", candidates.join(", "))]
+    Ambiguous {
+        selector: String,
+        candidates: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntitySelector(String);
+
+impl From<String> for EntitySelector {
+    fn from(selector: String) -> Self {
+        EntitySelector(selector)
+    }
+}
+
+impl EntitySelector {
+    /// Returns ID and path of the selected library entity.
+    pub fn resolve(&self, library: &Library) -> Result<(ID, PathBuf), EntitySelectorError> {
+        if let Some(stringified_id) = self.0.strip_prefix(ID_PREFIX) {
+            let id = stringified_id
+                .parse::<ID>()
+                .map_err(|_| EntitySelectorError::InvalidID(self.0.clone()))?;
+
+            return match library.get_path(id)? {
+                Some(path) => Ok((id, path)),
+                None => Err(EntitySelectorError::NotFound(self.0.clone())),
+            };
+        }
+
+        let path = PathBuf::from(&self.0);
+        if let Some(id) = library.get_id(path.clone())? {
+            return Ok((id, path));
+        }
+
+        if let Ok(id) = self.0.parse::<ID>() {
+            if let Some(path) = library.get_path(id)? {
+                return Ok((id, path));
+            }
+        }
+
+        let id = self.resolve_name(library)?;
+        match library.get_path(id)? {
+            Some(path) => Ok((id, path)),
+            None => Err(EntitySelectorError::NotFound(self.0.clone())),
+        }
+    }
+
+    pub fn resolve_id(&self, library: &Library) -> Result<ID, EntitySelectorError> {
+        Ok(self.resolve(library)?.0)
+    }
+
+    pub fn resolve_path(&self, library: &Library) -> Result<PathBuf, EntitySelectorError> {
+        Ok(self.resolve(library)?.1)
+    }
+
+    /// Exact names take precedence over prefixes, so the entity named `Algebra` is selected by
+    /// `Algebra` even if there's `Algebra II`.
+    fn resolve_name(&self, library: &Library) -> Result<ID, EntitySelectorError> {
+        let bases = library.get_bases()?;
+
+        let mut matched_bases = bases
+            .iter()
+            .filter(|base| base.name() == &self.0)
+            .collect::<Vec<_>>();
+        if matched_bases.is_empty() {
+            let lowercase_selector = self.0.to_lowercase();
+            matched_bases = bases
+                .iter()
+                .filter(|base| base.name().to_lowercase().starts_with(&lowercase_selector))
+                .collect();
+        }
+
+        match matched_bases.as_slice() {
+            [] => Err(EntitySelectorError::NotFound(self.0.clone())),
+            [base] => Ok(base.id()),
+            _ => Err(EntitySelectorError::Ambiguous {
+                selector: self.0.clone(),
+                candidates: matched_bases
+                    .iter()
+                    .map(|base| format!("'{}' (#{})", base.name(), base.id()))
+                    .collect(),
+            }),
+        }
+    }
+}
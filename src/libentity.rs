@@ -1,6 +1,7 @@
+use crate::bibliography::Bibliography;
 use crate::entity_base::EntityType;
 use crate::id::ID;
-use crate::progress::Progress;
+use crate::progress::{AggregateProgress, Progress};
 
 use std::path::PathBuf;
 
@@ -13,18 +14,40 @@ pub struct LibEntityData {
     pub tags: Vec<String>,
     pub progress: Option<Progress>,
     pub description: Option<String>,
+    pub bibliography: Option<Bibliography>,
 }
 
-/// Contains all attributes from `LibEntityData` + id.
+/// Library entity related with another one. It's the target of the relation for links and the
+/// source for backlinks.
+#[derive(Debug, Clone)]
+pub struct LinkedEntity {
+    pub relation: String,
+    pub id: ID,
+    pub name: String,
+}
+
+/// Contains all attributes from `LibEntityData` + id + names of collections containing the
+/// library entity + its relations with others.
 #[derive(Debug, Clone)]
 pub struct LibEntity {
     id: ID,
     data: LibEntityData,
+    collections: Vec<String>,
+    links: Vec<LinkedEntity>,
+    backlinks: Vec<LinkedEntity>,
+    aggregate_progress: Option<AggregateProgress>,
 }
 
 impl LibEntity {
     pub fn from_id_data(id: ID, data: LibEntityData) -> Self {
-        LibEntity { data, id }
+        LibEntity {
+            data,
+            id,
+            collections: Vec::new(),
+            links: Vec::new(),
+            backlinks: Vec::new(),
+            aggregate_progress: None,
+        }
     }
 
     pub fn path(&self) -> &PathBuf {
@@ -39,10 +62,19 @@ impl LibEntity {
         self.data.progress.as_ref()
     }
 
+    /// Progress of the documents beneath the section. Exists only for sections.
+    pub fn aggregate_progress(&self) -> Option<&AggregateProgress> {
+        self.aggregate_progress.as_ref()
+    }
+
     pub fn description(&self) -> Option<&String> {
         self.data.description.as_ref()
     }
 
+    pub fn bibliography(&self) -> Option<&Bibliography> {
+        self.data.bibliography.as_ref()
+    }
+
     pub fn name(&self) -> &String {
         &self.data.name
     }
@@ -59,6 +91,20 @@ impl LibEntity {
         &self.data.tags
     }
 
+    pub fn collections(&self) -> &Vec<String> {
+        &self.collections
+    }
+
+    /// Relations going from the library entity.
+    pub fn links(&self) -> &Vec<LinkedEntity> {
+        &self.links
+    }
+
+    /// Relations going to the library entity.
+    pub fn backlinks(&self) -> &Vec<LinkedEntity> {
+        &self.backlinks
+    }
+
     pub fn progress_mut(&mut self) -> Option<&mut Progress> {
         self.data.progress.as_mut()
     }
@@ -79,6 +125,27 @@ impl LibEntity {
         self.data.etype = new_etype;
     }
 
+    pub fn set_progress(&mut self, new_progress: Option<Progress>) {
+        self.data.progress = new_progress;
+    }
+
+    pub fn set_collections(&mut self, new_collections: Vec<String>) {
+        self.collections = new_collections;
+    }
+
+    pub fn set_aggregate_progress(&mut self, new_aggregate_progress: Option<AggregateProgress>) {
+        self.aggregate_progress = new_aggregate_progress;
+    }
+
+    pub fn set_links(&mut self, new_links: Vec<LinkedEntity>, new_backlinks: Vec<LinkedEntity>) {
+        self.links = new_links;
+        self.backlinks = new_backlinks;
+    }
+
+    pub fn set_backlinks(&mut self, new_backlinks: Vec<LinkedEntity>) {
+        self.backlinks = new_backlinks;
+    }
+
     pub fn tags_mut(&mut self) -> &mut Vec<String> {
         &mut self.data.tags
     }
@@ -0,0 +1,45 @@
+//! Here is the filter of library entities used by commands that process many library entities at
+//! once (e.g. `list` and `export`).
+
+use crate::comps_interaction::tag_matches;
+use crate::entity_base::Tag;
+use crate::libentity::LibEntity;
+
+#[derive(Debug, Clone, Default)]
+pub struct LibEntityFilter {
+    /// Library entity matches only if it has each of the tags or their descendants.
+    tags: Vec<Tag>,
+    /// Library entity matches only if its name or bibliography contains the text (case
+    /// insensitive).
+    search: Option<String>,
+}
+
+impl LibEntityFilter {
+    pub fn new(tags: Vec<Tag>, search: Option<String>) -> Self {
+        LibEntityFilter { tags, search }
+    }
+
+    pub fn matches(&self, libentity: &LibEntity) -> bool {
+        let tags_match = self.tags.iter().all(|filter| {
+            libentity
+                .tags()
+                .iter()
+                .any(|libentity_tag| tag_matches(libentity_tag, filter))
+        });
+
+        let search_matches = match &self.search {
+            Some(search) => {
+                let search = search.to_lowercase();
+
+                libentity.name().to_lowercase().contains(&search)
+                    || libentity
+                        .bibliography()
+                        .map(|bibliography| bibliography.matches(&search))
+                        .unwrap_or(false)
+            }
+            None => true,
+        };
+
+        tags_match && search_matches
+    }
+}
@@ -1,11 +1,14 @@
+use crate::bibliography::Bibliography;
 use crate::comps_interaction::libentity_has_progress;
 use crate::entity_base::{EntityBase, EntityType, Tag};
 use crate::id::ID;
-use crate::libentity::{LibEntity, LibEntityData};
-use crate::progress::Progress;
+use crate::libentity::{LibEntity, LibEntityData, LinkedEntity};
+use crate::progress::{AggregateProgress, Progress};
+use crate::relation::Relation;
 use crate::storage::{Storage, StorageError};
 
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 use thiserror::Error;
 
@@ -19,6 +22,27 @@ pub enum LibraryError {
     CouldNotFindElement { element: String, path: PathBuf },
     #[error("couldn't find {element} for library entity with ID {id}")]
     CouldNotFindElementWithID { element: String, id: ID },
+    #[error("couldn't change type of library entity with path '{path}'")]
+    CouldNotChangeEntityType { path: PathBuf },
+    #[error("couldn't find collection '{name}'")]
+    CouldNotFindCollection { name: String },
+}
+
+/// Collections and relations of library entities by their IDs. Collections are kept by their
+/// names and relations are kept by library entities they go from, so finding ones of a single
+/// library entity needs reading all of them.
+#[derive(Debug, Default)]
+struct Memberships {
+    collections: HashMap<ID, Vec<String>>,
+    links: HashMap<ID, Vec<Relation>>,
+    /// Relations going to the ID. Targets of the relations are their sources.
+    backlinks: HashMap<ID, Vec<Relation>>,
+}
+
+/// Returns `true` if the path is inside of the section (but isn't the section itself). Both paths
+/// must be simplified.
+fn is_beneath(path: &Path, section_path: &Path) -> bool {
+    path != section_path && path.starts_with(section_path)
 }
 
 /// Implements operations with library entities (`LibEntity`) through storage (`Storage`).
@@ -31,11 +55,126 @@ impl Library {
         Library { storage }
     }
 
+    /// Backlinks of the returned library entity aren't set, cause finding them needs reading
+    /// relations of all library entities. See `Self::with_backlinks`.
     pub fn get_libentity(&self, path: PathBuf) -> Result<Option<LibEntity>, LibraryError> {
+        match self.read_libentity(path)? {
+            Some(libentity) => Ok(Some(self.with_aggregate_progress(libentity)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Same as `Self::get_libentity` but aggregate progress isn't computed, cause it needs
+    /// scanning paths of the whole library.
+    fn read_libentity(&self, path: PathBuf) -> Result<Option<LibEntity>, LibraryError> {
         let id = match self.storage.get_id(path.clone())? {
             Some(id) => id,
             None => return Ok(None),
         };
+        let mut memberships = self.read_memberships_of(id)?;
+
+        Ok(Some(self.collect_libentity(id, path, &mut memberships)?))
+    }
+
+    /// Returns all library entities of the library.
+    pub fn get_libentities(&self) -> Result<Vec<LibEntity>, LibraryError> {
+        // Collections and relations are read once instead of reading them for each library
+        // entity.
+        let mut memberships = self.read_memberships()?;
+        let mut libentities = Vec::new();
+
+        for path in self.storage.keys_path()? {
+            let id = match self.storage.get_id(path.clone())? {
+                Some(id) => id,
+                None => return Err(LibraryError::CouldNotFindLibEntity { path }),
+            };
+
+            libentities.push(self.collect_libentity(id, path, &mut memberships)?);
+        }
+
+        // Aggregate progresses are computed from the collected library entities in one pass:
+        // progress of each document is added to all sections among its ancestors.
+        let mut aggregate_progresses: HashMap<PathBuf, AggregateProgress> = libentities
+            .iter()
+            .filter(|libentity| libentity.etype() == EntityType::Section)
+            .map(|section| (section.path().clone(), AggregateProgress::default()))
+            .collect();
+        for libentity in &libentities {
+            if let Some(progress) = libentity.progress() {
+                for ancestor in libentity.path().ancestors().skip(1) {
+                    if let Some(aggregate_progress) = aggregate_progresses.get_mut(ancestor) {
+                        aggregate_progress.add(progress);
+                    }
+                }
+            }
+        }
+        for libentity in &mut libentities {
+            if let Some(aggregate_progress) = aggregate_progresses.remove(libentity.path()) {
+                libentity.set_aggregate_progress(Some(aggregate_progress));
+            }
+        }
+
+        Ok(libentities)
+    }
+
+    /// Backlinks of the returned library entity aren't set as in `Self::get_libentity`.
+    pub fn get_libentity_by_id(&self, id: ID) -> Result<Option<LibEntity>, LibraryError> {
+        let path = match self.storage.get_path(id)? {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+        let mut memberships = self.read_memberships_of(id)?;
+        let libentity = self.collect_libentity(id, path, &mut memberships)?;
+
+        Ok(Some(self.with_aggregate_progress(libentity)?))
+    }
+
+    /// Sets aggregate progress of the library entity if it's a section.
+    fn with_aggregate_progress(&self, mut libentity: LibEntity) -> Result<LibEntity, LibraryError> {
+        if libentity.etype() == EntityType::Section {
+            libentity.set_aggregate_progress(Some(self.get_aggregate_progress(libentity.id())?));
+        }
+
+        Ok(libentity)
+    }
+
+    /// Returns progress of all documents beneath the section with the given ID.
+    pub fn get_aggregate_progress(&self, id: ID) -> Result<AggregateProgress, LibraryError> {
+        let section_path = match self.storage.get_path(id)? {
+            Some(section_path) => section_path,
+            None => {
+                return Err(LibraryError::CouldNotFindElementWithID {
+                    element: "path".to_string(),
+                    id,
+                })
+            }
+        };
+
+        let mut aggregate_progress = AggregateProgress::default();
+        for path in self.storage.keys_path()? {
+            if !is_beneath(&path, &section_path) {
+                continue;
+            }
+
+            // Only documents have progress.
+            if let Some(document_id) = self.storage.get_id(path)? {
+                if let Some(progress) = self.storage.get_progress(document_id)? {
+                    aggregate_progress.add(&progress);
+                }
+            }
+        }
+
+        Ok(aggregate_progress)
+    }
+
+    /// Collects all components of the library entity with the given ID and path. Collections and
+    /// relations of the ID are taken from `memberships`.
+    fn collect_libentity(
+        &self,
+        id: ID,
+        path: PathBuf,
+        memberships: &mut Memberships,
+    ) -> Result<LibEntity, LibraryError> {
         let base = match self.storage.get_entitybase(id)? {
             Some(base) => base,
             None => {
@@ -59,6 +198,7 @@ impl Library {
             None
         };
         let description = self.storage.get_description(id)?;
+        let bibliography = self.storage.get_bibliography(id)?;
 
         let libentity_data = LibEntityData {
             path,
@@ -67,10 +207,34 @@ impl Library {
             tags: base.tags().clone(),
             progress,
             description,
+            bibliography,
         };
-        let libentity = LibEntity::from_id_data(id, libentity_data);
+        let mut libentity = LibEntity::from_id_data(id, libentity_data);
+        libentity.set_collections(memberships.collections.remove(&id).unwrap_or_default());
+        libentity.set_links(
+            self.linked_entities(memberships.links.remove(&id).unwrap_or_default())?,
+            self.linked_entities(memberships.backlinks.remove(&id).unwrap_or_default())?,
+        );
 
-        Ok(Some(libentity))
+        Ok(libentity)
+    }
+
+    /// Adds names of the targets to the relations. Relations whose targets don't exist are
+    /// skipped.
+    fn linked_entities(&self, relations: Vec<Relation>) -> Result<Vec<LinkedEntity>, LibraryError> {
+        let mut linked_entities = Vec::with_capacity(relations.len());
+
+        for relation in relations {
+            if let Some(base) = self.storage.get_entitybase(relation.target)? {
+                linked_entities.push(LinkedEntity {
+                    relation: relation.kind,
+                    id: relation.target,
+                    name: base.name().clone(),
+                });
+            }
+        }
+
+        Ok(linked_entities)
     }
 
     pub fn add_libentity(&mut self, libentity_data: LibEntityData) -> Result<(), LibraryError> {
@@ -81,6 +245,7 @@ impl Library {
             tags,
             progress,
             description,
+            bibliography,
         } = libentity_data;
 
         let id = self.storage.link_id_to_path(path)?;
@@ -95,6 +260,10 @@ impl Library {
             self.storage.link_description_to_id(id, description)?;
         }
 
+        if let Some(bibliography) = bibliography {
+            self.storage.link_bibliography_to_id(id, bibliography)?;
+        }
+
         Ok(())
     }
 
@@ -110,26 +279,66 @@ impl Library {
             Some(_) => Some(self.storage.unlink_description_from_id(id)?),
             None => None,
         };
+        let bibliography = match self.storage.get_bibliography(id)? {
+            Some(_) => Some(self.storage.unlink_bibliography_from_id(id)?),
+            None => None,
+        };
+        let collections = self.get_collections_of(id)?;
+        for name in &collections {
+            self.remove_from_collection(name, &[id])?;
+        }
+        self.del_relations_of(id)?;
 
         let libentity_data = LibEntityData {
             path,
             progress,
             description,
+            bibliography,
             name: base.name().clone(),
             etype: base.etype(),
             tags: base.tags().clone(),
         };
-        let libentity = LibEntity::from_id_data(id, libentity_data);
+        let mut libentity = LibEntity::from_id_data(id, libentity_data);
+        libentity.set_collections(collections);
 
         Ok(libentity)
     }
 
+    /// Updates attributes of the library entity associated with the path of the given data. ID and
+    /// type of the entity aren't changed. Returns the old library entity without aggregate
+    /// progress.
     pub fn update_libentity(
         &mut self,
         new_libentity_data: LibEntityData,
     ) -> Result<LibEntity, LibraryError> {
-        let old_libentity = self.del_libentity(new_libentity_data.path.clone())?;
-        self.add_libentity(new_libentity_data)?;
+        let LibEntityData {
+            path,
+            name,
+            etype,
+            tags,
+            progress,
+            description,
+            bibliography,
+        } = new_libentity_data;
+
+        let old_libentity = match self.read_libentity(path.clone())? {
+            Some(old_libentity) => old_libentity,
+            None => return Err(LibraryError::CouldNotFindLibEntity { path }),
+        };
+        if old_libentity.etype() != etype {
+            return Err(LibraryError::CouldNotChangeEntityType { path });
+        }
+        let id = old_libentity.id();
+
+        self.storage
+            .update_entitybase(id, EntityBase::new(id, name, etype, tags))?;
+
+        if let Some(progress) = progress {
+            self.storage.update_progress(id, progress)?;
+        }
+
+        self.set_description(id, description)?;
+        self.set_bibliography(id, bibliography)?;
 
         Ok(old_libentity)
     }
@@ -138,6 +347,10 @@ impl Library {
         Ok(self.storage.get_id(path)?)
     }
 
+    pub fn get_path(&self, id: ID) -> Result<Option<PathBuf>, LibraryError> {
+        Ok(self.storage.get_path(id)?)
+    }
+
     pub fn get_progress(&self, id: ID) -> Result<Option<Progress>, LibraryError> {
         Ok(self.storage.get_progress(id)?)
     }
@@ -146,6 +359,332 @@ impl Library {
         Ok(self.storage.get_description(id)?)
     }
 
+    pub fn get_bibliography(&self, id: ID) -> Result<Option<Bibliography>, LibraryError> {
+        Ok(self.storage.get_bibliography(id)?)
+    }
+
+    /// Links, replaces or (if `None` is given) unlinks the description of the ID. Returns the old
+    /// description.
+    pub fn set_description(
+        &mut self,
+        id: ID,
+        description: Option<String>,
+    ) -> Result<Option<String>, LibraryError> {
+        let old_description = self.storage.get_description(id)?;
+
+        match (&old_description, description) {
+            (Some(_), Some(description)) => {
+                self.storage.update_description(id, description)?;
+            }
+            (Some(_), None) => {
+                self.storage.unlink_description_from_id(id)?;
+            }
+            (None, Some(description)) => {
+                self.storage.link_description_to_id(id, description)?;
+            }
+            (None, None) => (),
+        }
+
+        Ok(old_description)
+    }
+
+    /// Links, replaces or (if `None` is given) unlinks the bibliography of the ID. Returns the old
+    /// bibliography.
+    pub fn set_bibliography(
+        &mut self,
+        id: ID,
+        bibliography: Option<Bibliography>,
+    ) -> Result<Option<Bibliography>, LibraryError> {
+        let old_bibliography = self.storage.get_bibliography(id)?;
+
+        match (&old_bibliography, bibliography) {
+            (Some(_), Some(bibliography)) => {
+                self.storage.update_bibliography(id, bibliography)?;
+            }
+            (Some(_), None) => {
+                self.storage.unlink_bibliography_from_id(id)?;
+            }
+            (None, Some(bibliography)) => {
+                self.storage.link_bibliography_to_id(id, bibliography)?;
+            }
+            (None, None) => (),
+        }
+
+        Ok(old_bibliography)
+    }
+
+    /// Replaces tags of the ID. Returns the old tags.
+    pub fn set_tags(&mut self, id: ID, tags: Vec<Tag>) -> Result<Vec<Tag>, LibraryError> {
+        let mut entitybase = match self.storage.get_entitybase(id)? {
+            Some(entitybase) => entitybase,
+            None => {
+                return Err(LibraryError::CouldNotFindElementWithID {
+                    element: "entitybase".to_string(),
+                    id,
+                })
+            }
+        };
+
+        let old_tags = std::mem::replace(entitybase.tags_mut(), tags);
+        self.storage.update_entitybase(id, entitybase)?;
+
+        Ok(old_tags)
+    }
+
+    /// Returns names of all collections in alphabetical order.
+    pub fn get_collection_names(&self) -> Result<Vec<String>, LibraryError> {
+        let mut names = self.storage.keys_collection()?;
+        names.sort();
+
+        Ok(names)
+    }
+
+    /// Returns IDs of the members of the collection in order of the collection.
+    pub fn get_collection(&self, name: &str) -> Result<Option<Vec<ID>>, LibraryError> {
+        Ok(self.storage.get_collection(name.to_string())?)
+    }
+
+    /// Returns names of the collections containing the ID in alphabetical order.
+    pub fn get_collections_of(&self, id: ID) -> Result<Vec<String>, LibraryError> {
+        let mut names = Vec::new();
+        for name in self.get_collection_names()? {
+            if self
+                .get_collection(&name)?
+                .unwrap_or_default()
+                .contains(&id)
+            {
+                names.push(name);
+            }
+        }
+
+        Ok(names)
+    }
+
+    /// Returns relations going to the ID. Targets of the relations are their sources.
+    pub fn get_backlinks(&self, id: ID) -> Result<Vec<Relation>, LibraryError> {
+        let mut sources = self.storage.keys_relations()?;
+        sources.sort();
+
+        let mut backlinks = Vec::new();
+        for source in sources {
+            for relation in self.get_relations(source)? {
+                if relation.target == id {
+                    backlinks.push(Relation {
+                        kind: relation.kind,
+                        target: source,
+                    });
+                }
+            }
+        }
+
+        Ok(backlinks)
+    }
+
+    /// Sets backlinks of the library entity taken by `Self::get_libentity` or
+    /// `Self::get_libentity_by_id`.
+    pub fn with_backlinks(&self, mut libentity: LibEntity) -> Result<LibEntity, LibraryError> {
+        let backlinks = self.linked_entities(self.get_backlinks(libentity.id())?)?;
+        libentity.set_backlinks(backlinks);
+
+        Ok(libentity)
+    }
+
+    /// Reads collections and links of the single ID. Backlinks aren't read.
+    fn read_memberships_of(&self, id: ID) -> Result<Memberships, LibraryError> {
+        let mut memberships = Memberships::default();
+        memberships
+            .collections
+            .insert(id, self.get_collections_of(id)?);
+        memberships.links.insert(id, self.get_relations(id)?);
+
+        Ok(memberships)
+    }
+
+    fn read_memberships(&self) -> Result<Memberships, LibraryError> {
+        let mut memberships = Memberships::default();
+
+        for name in self.get_collection_names()? {
+            for id in self.get_collection(&name)?.unwrap_or_default() {
+                memberships
+                    .collections
+                    .entry(id)
+                    .or_default()
+                    .push(name.clone());
+            }
+        }
+
+        let mut sources = self.storage.keys_relations()?;
+        sources.sort();
+        for source in sources {
+            let relations = self.get_relations(source)?;
+
+            for relation in &relations {
+                memberships
+                    .backlinks
+                    .entry(relation.target)
+                    .or_default()
+                    .push(Relation {
+                        kind: relation.kind.clone(),
+                        target: source,
+                    });
+            }
+            memberships.links.insert(source, relations);
+        }
+
+        Ok(memberships)
+    }
+
+    /// Returns relations going from the ID in order of their adding.
+    pub fn get_relations(&self, id: ID) -> Result<Vec<Relation>, LibraryError> {
+        Ok(self.storage.get_relations(id)?.unwrap_or_default())
+    }
+
+    /// Replaces relations going from the ID. Empty relations are unlinked.
+    fn set_relations(&mut self, id: ID, relations: Vec<Relation>) -> Result<(), LibraryError> {
+        match (
+            self.storage.get_relations(id)?.is_some(),
+            relations.is_empty(),
+        ) {
+            (true, true) => {
+                self.storage.unlink_relations_from_id(id)?;
+            }
+            (true, false) => {
+                self.storage.update_relations(id, relations)?;
+            }
+            (false, false) => self.storage.link_relations_to_id(id, relations)?,
+            (false, true) => (),
+        }
+
+        Ok(())
+    }
+
+    /// Adds the relation going from the ID. Returns `false` if there's such relation already.
+    pub fn add_relation(&mut self, id: ID, relation: Relation) -> Result<bool, LibraryError> {
+        let mut relations = self.get_relations(id)?;
+        if relations.contains(&relation) {
+            return Ok(false);
+        }
+
+        relations.push(relation);
+        self.set_relations(id, relations)?;
+
+        Ok(true)
+    }
+
+    /// Removes the relation going from the ID. Returns `false` if there's no such relation.
+    pub fn remove_relation(&mut self, id: ID, relation: &Relation) -> Result<bool, LibraryError> {
+        let relations = self.get_relations(id)?;
+        if !relations.contains(relation) {
+            return Ok(false);
+        }
+
+        self.set_relations(
+            id,
+            relations
+                .into_iter()
+                .filter(|other_relation| other_relation != relation)
+                .collect(),
+        )?;
+
+        Ok(true)
+    }
+
+    /// Removes all relations going from and to the ID.
+    fn del_relations_of(&mut self, id: ID) -> Result<(), LibraryError> {
+        self.set_relations(id, Vec::new())?;
+
+        for source in self.storage.keys_relations()? {
+            let relations = self.get_relations(source)?;
+            if relations.iter().any(|relation| relation.target == id) {
+                self.set_relations(
+                    source,
+                    relations
+                        .into_iter()
+                        .filter(|relation| relation.target != id)
+                        .collect(),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn create_collection(&mut self, name: &str) -> Result<(), LibraryError> {
+        Ok(self
+            .storage
+            .create_collection(name.to_string(), Vec::new())?)
+    }
+
+    /// Deletes the collection. Its members stay in the library. Returns IDs of the members.
+    pub fn delete_collection(&mut self, name: &str) -> Result<Vec<ID>, LibraryError> {
+        if self.get_collection(name)?.is_none() {
+            return Err(LibraryError::CouldNotFindCollection {
+                name: name.to_string(),
+            });
+        }
+
+        Ok(self.storage.delete_collection(name.to_string())?)
+    }
+
+    /// Inserts the IDs into the collection at the position (counted from zero) or appends them if
+    /// the position isn't given. IDs that are already in the collection are skipped. Returns count
+    /// of the inserted IDs.
+    pub fn add_to_collection(
+        &mut self,
+        name: &str,
+        ids: &[ID],
+        position: Option<usize>,
+    ) -> Result<usize, LibraryError> {
+        let mut members = match self.get_collection(name)? {
+            Some(members) => members,
+            None => {
+                return Err(LibraryError::CouldNotFindCollection {
+                    name: name.to_string(),
+                })
+            }
+        };
+
+        let mut new_members = Vec::new();
+        for id in ids {
+            if !members.contains(id) && !new_members.contains(id) {
+                new_members.push(*id);
+            }
+        }
+        let inserted_count = new_members.len();
+
+        let position = position.unwrap_or(members.len()).min(members.len());
+        members.splice(position..position, new_members);
+        self.storage.update_collection(name.to_string(), members)?;
+
+        Ok(inserted_count)
+    }
+
+    /// Removes the IDs from the collection. Returns count of the removed IDs.
+    pub fn remove_from_collection(
+        &mut self,
+        name: &str,
+        ids: &[ID],
+    ) -> Result<usize, LibraryError> {
+        let members = match self.get_collection(name)? {
+            Some(members) => members,
+            None => {
+                return Err(LibraryError::CouldNotFindCollection {
+                    name: name.to_string(),
+                })
+            }
+        };
+
+        let old_count = members.len();
+        let members = members
+            .into_iter()
+            .filter(|id| !ids.contains(id))
+            .collect::<Vec<_>>();
+        let removed_count = old_count - members.len();
+        self.storage.update_collection(name.to_string(), members)?;
+
+        Ok(removed_count)
+    }
+
     pub fn get_name(&self, id: ID) -> Result<Option<String>, LibraryError> {
         Ok(self
             .storage
@@ -168,6 +707,69 @@ impl Library {
         Ok(self.storage.get_entitybase(id)?)
     }
 
+    /// Returns entitybases of all library entities.
+    pub fn get_bases(&self) -> Result<Vec<EntityBase>, LibraryError> {
+        let mut bases = Vec::new();
+
+        for id in self.storage.keys_id()? {
+            match self.storage.get_entitybase(id)? {
+                Some(base) => bases.push(base),
+                None => {
+                    return Err(LibraryError::CouldNotFindElementWithID {
+                        element: "entitybase".to_string(),
+                        id,
+                    })
+                }
+            }
+        }
+
+        Ok(bases)
+    }
+
+    /// Replaces tags of each library entity by the result of `rewrite`. Either all entitybases
+    /// are updated or none: if an update fails, the already updated entitybases are restored.
+    /// Returns count of the library entities whose tags were changed.
+    pub fn rewrite_tags<F>(&mut self, rewrite: F) -> Result<usize, LibraryError>
+    where
+        F: Fn(&[Tag]) -> Vec<Tag>,
+    {
+        let mut changes = Vec::new();
+        for old_base in self.get_bases()? {
+            let new_tags = rewrite(old_base.tags());
+
+            if &new_tags != old_base.tags() {
+                let new_base = EntityBase::new(
+                    old_base.id(),
+                    old_base.name().clone(),
+                    old_base.etype(),
+                    new_tags,
+                );
+                changes.push((old_base, new_base));
+            }
+        }
+
+        let mut updated_bases: Vec<&EntityBase> = Vec::with_capacity(changes.len());
+        for (old_base, new_base) in &changes {
+            if let Err(err) = self
+                .storage
+                .update_entitybase(new_base.id(), new_base.clone())
+            {
+                // Errors of restoring are ignored because the first error is more important.
+                for updated_base in updated_bases {
+                    _ = self
+                        .storage
+                        .update_entitybase(updated_base.id(), updated_base.clone());
+                }
+
+                return Err(err.into());
+            }
+
+            updated_bases.push(old_base);
+        }
+
+        Ok(changes.len())
+    }
+
     pub unsafe fn storage(&self) -> &Storage {
         &self.storage
     }
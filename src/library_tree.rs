@@ -0,0 +1,126 @@
+//! Here is the tree of the library: library entities nested under their parent directories.
+//! Directories that aren't tracked as sections are kept in the tree as untracked nodes, so the
+//! nesting is visible even if intermediate directories aren't added.
+
+use crate::entity_base::EntityType;
+use crate::libentity::LibEntity;
+
+use std::path::{Component, Path, PathBuf};
+
+/// Name of the root node when the tree is built for the whole library.
+const LIBRARY_ROOT_NAME: &str = ".";
+
+#[derive(Debug, Clone)]
+pub struct LibraryTree {
+    /// Name of the file or directory.
+    pub name: String,
+    pub path: PathBuf,
+    /// `None` for untracked directories and the root of the library.
+    pub libentity: Option<LibEntity>,
+    pub children: Vec<LibraryTree>,
+}
+
+impl LibraryTree {
+    fn new(name: String, path: PathBuf, libentity: Option<LibEntity>) -> Self {
+        LibraryTree {
+            name,
+            path,
+            libentity,
+            children: Vec::new(),
+        }
+    }
+
+    /// Makes the tree of the library entities beneath the section. If no section is given, the
+    /// tree of the whole library is made. Library entities that aren't beneath the root are
+    /// skipped.
+    pub fn build(section: Option<LibEntity>, libentities: Vec<LibEntity>) -> Self {
+        let mut tree = match section {
+            Some(section) => LibraryTree::new(
+                file_name(section.path()),
+                section.path().clone(),
+                Some(section),
+            ),
+            None => LibraryTree::new(LIBRARY_ROOT_NAME.to_string(), PathBuf::new(), None),
+        };
+
+        for libentity in libentities {
+            let components = match libentity.path().strip_prefix(&tree.path) {
+                Ok(relative_path) => relative_path
+                    .components()
+                    .filter_map(|component| match component {
+                        Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>(),
+                Err(_) => continue,
+            };
+
+            // The root itself.
+            if components.is_empty() {
+                continue;
+            }
+
+            tree.insert(&components, libentity);
+        }
+        tree.sort();
+
+        tree
+    }
+
+    /// Untracked nodes are always directories because they're made only for parents of library
+    /// entities.
+    pub fn is_directory(&self) -> bool {
+        self.libentity
+            .as_ref()
+            .map(|libentity| libentity.etype() == EntityType::Section)
+            .unwrap_or(true)
+    }
+
+    pub fn is_tracked(&self) -> bool {
+        self.libentity.is_some()
+    }
+
+    /// Inserts the library entity by components of its path relative to the node. Missing
+    /// intermediate nodes are made untracked.
+    fn insert(&mut self, components: &[String], libentity: LibEntity) {
+        let (name, rest) = match components.split_first() {
+            Some(split) => split,
+            None => return,
+        };
+
+        let child_index = match self.children.iter().position(|child| child.name == *name) {
+            Some(child_index) => child_index,
+            None => {
+                self.children
+                    .push(LibraryTree::new(name.clone(), self.path.join(name), None));
+                self.children.len() - 1
+            }
+        };
+        let child = &mut self.children[child_index];
+
+        if rest.is_empty() {
+            child.libentity = Some(libentity);
+        } else {
+            child.insert(rest, libentity);
+        }
+    }
+
+    /// Directories go before files, nodes of the same kind are sorted by name.
+    fn sort(&mut self) {
+        self.children.sort_by(|a, b| {
+            b.is_directory()
+                .cmp(&a.is_directory())
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        for child in &mut self.children {
+            child.sort();
+        }
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
@@ -0,0 +1,186 @@
+//! Here is walking through directories of the library. Hidden entries, the working directory and
+//! ignored entries are skipped.
+//!
+//! Entries are ignored if they're listed in the `--ignore` option of a command or match patterns
+//! of ignore files. There're two ignore files: the global one in the config directory and the
+//! local one in the root of the library. Both have gitignore syntax, patterns of the local file
+//! take precedence over the global ones.
+
+use crate::global_conf_directory::{configdir, GlobalConfError};
+use crate::library::{Library, LibraryError};
+use crate::storage::DEFAULT_WORKING_DIR;
+
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Error as IgnoreError;
+use thiserror::Error;
+use walkdir::{DirEntry, Error as WalkdirError, WalkDir};
+
+/// Name of the ignore file. It's looked for in the root of the library and in the config
+/// directory.
+pub const IGNORE_FILE: &str = ".popuskignore";
+
+#[derive(Debug, Error)]
+pub enum LibraryWalkerError {
+    #[error("couldn't get directory entry: {0}")]
+    Walkdir(#[from] WalkdirError),
+    #[error("library error: {0}")]
+    Library(#[from] LibraryError),
+    #[error("global conf: {0}")]
+    GlobalConfError(#[from] GlobalConfError),
+    #[error("ignore file: {0}")]
+    IgnoreFile(#[from] IgnoreError),
+}
+
+/// Builds matcher of patterns from the global and the local ignore files. Missing files are
+/// skipped.
+fn read_ignore_files() -> Result<Gitignore, LibraryWalkerError> {
+    // The root of the library is the current directory.
+    let mut builder = GitignoreBuilder::new(".");
+
+    // The local ignore file is added last so that its patterns override the global ones.
+    for ignore_file in [configdir()?.join(IGNORE_FILE), PathBuf::from(IGNORE_FILE)] {
+        if !ignore_file.is_file() {
+            continue;
+        }
+
+        if let Some(ignore_error) = builder.add(&ignore_file) {
+            return Err(ignore_error.into());
+        }
+    }
+
+    Ok(builder.build()?)
+}
+
+fn is_hidden(name: &OsStr) -> bool {
+    name.to_str()
+        .map(|s| s != "." && s != ".." && s.starts_with('.'))
+        .unwrap_or(false)
+}
+
+/// Just returns `path == "."`
+fn is_current_directory(path: &Path) -> bool {
+    path.to_str()
+        .map(|path_as_str| path_as_str == ".")
+        .unwrap_or(false)
+}
+
+/// Just returns `path == ".popusk"`.
+fn is_working_directory(name: &OsStr) -> bool {
+    name.to_str()
+        .map(|path| path.contains(DEFAULT_WORKING_DIR))
+        .unwrap_or(false)
+}
+
+#[derive(Debug, Clone)]
+pub struct LibraryWalker {
+    show_hidden: bool,
+    ignore: Vec<String>,
+}
+
+impl LibraryWalker {
+    pub fn new(show_hidden: bool, ignore: Vec<String>) -> Self {
+        LibraryWalker {
+            show_hidden,
+            ignore,
+        }
+    }
+
+    fn hide_hidden(&self) -> bool {
+        !self.show_hidden
+    }
+
+    fn ignore_list_contains(&self, name: &OsStr) -> bool {
+        self.ignore
+            .iter()
+            .position(|a| name == OsStr::new(a))
+            .is_some()
+    }
+
+    fn check_entry(&self, entry: &DirEntry, ignore_files: &Gitignore) -> bool {
+        let name = entry.file_name();
+
+        // Patterns like `*` mustn't hide the root of the library itself.
+        if !is_current_directory(entry.path())
+            && ignore_files
+                .matched(entry.path(), entry.file_type().is_dir())
+                .is_ignore()
+        {
+            return false;
+        }
+
+        // hide_hidden, is_hidden, ignore, is_working_directory
+        //
+        // - hide_hidden && is_hidden => false
+        // - hide_hidden && !is_hidden && ignore => false
+        // - hide_hidden && !is_hidden && !ignore => true
+        // - !hide_hidden && is_working_directory => false
+        // - !hide_hidden && !is_working_directory && ignore => false
+        // - !hide_hidden && !is_working_directory && !ignore => true
+        if self.hide_hidden() {
+            if is_hidden(name) {
+                false
+            } else {
+                if self.ignore_list_contains(name) {
+                    false
+                } else {
+                    true
+                }
+            }
+        } else if is_working_directory(name) {
+            false
+        } else {
+            if self.ignore_list_contains(name) {
+                false
+            } else {
+                true
+            }
+        }
+    }
+
+    /// Returns paths of `root` and, if `recursive` is set, of all entries beneath it. The current
+    /// directory itself is never returned.
+    pub fn walk(&self, root: &Path, recursive: bool) -> Result<Vec<PathBuf>, LibraryWalkerError> {
+        let ignore_files = read_ignore_files()?;
+
+        let mut walkdir = WalkDir::new(root);
+        if !recursive {
+            walkdir = walkdir.max_depth(0);
+        }
+
+        let directory_rec_iterator = walkdir
+            .into_iter()
+            .filter_entry(|entry| self.check_entry(entry, &ignore_files))
+            .filter(|maybe_entry| match maybe_entry {
+                Ok(ref entry) => !is_current_directory(entry.path()),
+                Err(_) => true,
+            });
+
+        let mut paths = Vec::new();
+        for entry in directory_rec_iterator {
+            paths.push(entry?.path().to_owned());
+        }
+
+        Ok(paths)
+    }
+
+    /// The same as `Self::walk` but returns only paths that aren't tracked by the library.
+    pub fn untracked_paths(
+        &self,
+        library: &Library,
+        root: &Path,
+        recursive: bool,
+    ) -> Result<Vec<PathBuf>, LibraryWalkerError> {
+        let mut untracked_paths = Vec::new();
+
+        for path in self.walk(root, recursive)? {
+            if library.get_id(path.clone())?.is_none() {
+                untracked_paths.push(path);
+            }
+        }
+
+        Ok(untracked_paths)
+    }
+}
@@ -0,0 +1,209 @@
+//! Here is the library as it's seen by scripts: the `popusk` global of the Lua state. Scripts get
+//! their own instance of `Library`, so functions defined in Lua don't borrow the library of the
+//! running command. The library is opened at the first call, so scripts that don't use it work
+//! outside of libraries.
+
+use crate::comps_interaction::tag_is_valid;
+use crate::config::Config;
+use crate::entity_base::Tag;
+use crate::entity_selector::{EntitySelector, EntitySelectorError};
+use crate::id::ID;
+use crate::libentity::LibEntity;
+use crate::libentity_filter::LibEntityFilter;
+use crate::library::{Library, LibraryError};
+use crate::storage::{Storage, StorageError};
+
+use itertools::Itertools;
+use mlua::{
+    Error as LuaError, Lua, Result as LuaResult, UserData as LuaUserData,
+    UserDataMethods as LuaUserDataMethods,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum LuaLibraryError {
+    #[error("storage error: {0}")]
+    Storage(#[from] StorageError),
+    #[error("library error: {0}")]
+    Library(#[from] LibraryError),
+    #[error("{0}")]
+    EntitySelector(#[from] EntitySelectorError),
+    #[error("scripts can't modify the library: 'scripts_modify_library' is disabled in config")]
+    ModificationDisabled,
+    #[error("invalid tag '{0}'")]
+    InvalidTag(String),
+    #[error("couldn't find library entity '{0}'")]
+    LibEntityNotFound(String),
+}
+
+/// The library and config available for scripts. It's kept as application data of the Lua state.
+pub struct LuaLibrary {
+    library: Option<Library>,
+    config: Config,
+    dry_run: bool,
+}
+
+impl LuaLibrary {
+    pub fn new(config: Config) -> Self {
+        LuaLibrary {
+            library: None,
+            config,
+            dry_run: false,
+        }
+    }
+
+    /// In the dry run, modifying functions check their arguments but don't change the library.
+    /// It's used while checking the scripts file with sample library entities.
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
+    fn library(&mut self) -> Result<&mut Library, LuaLibraryError> {
+        let library = match self.library.take() {
+            Some(library) => library,
+            None => Library::new(Storage::open()?),
+        };
+
+        Ok(self.library.insert(library))
+    }
+
+    fn resolve_id(&mut self, selector: String) -> Result<Option<ID>, LuaLibraryError> {
+        match EntitySelector::from(selector).resolve_id(self.library()?) {
+            Ok(id) => Ok(Some(id)),
+            Err(EntitySelectorError::NotFound(_)) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn check_modification(&self) -> Result<(), LuaLibraryError> {
+        match self.config.scripts_modify_library() {
+            true => Ok(()),
+            false => Err(LuaLibraryError::ModificationDisabled),
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn list(&mut self) -> Result<Vec<LibEntity>, LuaLibraryError> {
+        let mut libentities = self.library()?.get_libentities()?;
+        libentities.sort_by_key(|libentity| libentity.id());
+
+        Ok(libentities)
+    }
+
+    /// Returns `None` if nothing matches the selector. Selectors matching several library
+    /// entities are errors.
+    pub fn get(&mut self, selector: String) -> Result<Option<LibEntity>, LuaLibraryError> {
+        let id = match self.resolve_id(selector)? {
+            Some(id) => id,
+            None => return Ok(None),
+        };
+
+        let library = self.library()?;
+        match library.get_libentity_by_id(id)? {
+            Some(libentity) => Ok(Some(library.with_backlinks(libentity)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns library entities whose name or bibliography contains the query (case
+    /// insensitive).
+    pub fn find(&mut self, query: String) -> Result<Vec<LibEntity>, LuaLibraryError> {
+        let filter = LibEntityFilter::new(Vec::new(), Some(query));
+
+        Ok(self
+            .list()?
+            .into_iter()
+            .filter(|libentity| filter.matches(libentity))
+            .collect())
+    }
+
+    /// Replaces tags of the library entity. Available only if `scripts_modify_library` is enabled.
+    pub fn set_tags(&mut self, selector: String, tags: Vec<Tag>) -> Result<(), LuaLibraryError> {
+        self.check_modification()?;
+
+        if let Some(tag) = tags.iter().find(|tag| !tag_is_valid(tag)) {
+            return Err(LuaLibraryError::InvalidTag(tag.clone()));
+        }
+        if self.dry_run {
+            return Ok(());
+        }
+        let id = match self.resolve_id(selector.clone())? {
+            Some(id) => id,
+            None => return Err(LuaLibraryError::LibEntityNotFound(selector)),
+        };
+
+        self.library()?
+            .set_tags(id, tags.into_iter().unique().collect())?;
+
+        Ok(())
+    }
+
+    /// Replaces or (if `None` is given) removes the description of the library entity. Available
+    /// only if `scripts_modify_library` is enabled.
+    pub fn set_description(
+        &mut self,
+        selector: String,
+        description: Option<String>,
+    ) -> Result<(), LuaLibraryError> {
+        self.check_modification()?;
+        if self.dry_run {
+            return Ok(());
+        }
+
+        let id = match self.resolve_id(selector.clone())? {
+            Some(id) => id,
+            None => return Err(LuaLibraryError::LibEntityNotFound(selector)),
+        };
+
+        self.library()?.set_description(id, description)?;
+
+        Ok(())
+    }
+}
+
+/// Calls the function with the library kept in the Lua state.
+fn with_library<R, F>(lua: &Lua, func: F) -> LuaResult<R>
+where
+    F: FnOnce(&mut LuaLibrary) -> Result<R, LuaLibraryError>,
+{
+    match lua.app_data_mut::<LuaLibrary>() {
+        Some(mut library) => func(&mut library).map_err(LuaError::external),
+        None => Err(LuaError::RuntimeError(
+            "the library isn't available for scripts".to_string(),
+        )),
+    }
+}
+
+/// Value of the `popusk` global. Its functions are called with dot: `popusk.get(selector)`.
+pub struct PopuskModule;
+
+impl LuaUserData for PopuskModule {
+    fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("list", |lua, ()| {
+            with_library(lua, |library| library.list())
+        });
+        methods.add_function("get", |lua, selector: String| {
+            with_library(lua, |library| library.get(selector))
+        });
+        methods.add_function("find", |lua, query: String| {
+            with_library(lua, |library| library.find(query))
+        });
+        methods.add_function("config", |lua, ()| {
+            with_library(lua, |library| Ok(library.config().clone()))
+        });
+        methods.add_function("set_tags", |lua, (selector, tags): (String, Vec<Tag>)| {
+            with_library(lua, |library| library.set_tags(selector, tags))
+        });
+        methods.add_function(
+            "set_description",
+            |lua, (selector, description): (String, Option<String>)| {
+                with_library(lua, |library| {
+                    library.set_description(selector, description)
+                })
+            },
+        );
+    }
+}
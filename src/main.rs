@@ -4,21 +4,31 @@
 use clap::Parser;
 
 mod app;
+mod bibliography;
+mod bibtex;
 mod comps_appearance;
 mod comps_interaction;
 mod config;
 mod core_commands;
+mod editor;
 mod entity_base;
+mod entity_selector;
 mod error_ext;
 mod global_conf_directory;
 mod id;
 mod libentity;
+mod libentity_filter;
 mod library;
+mod library_tree;
+mod library_walker;
+mod lua_library;
+mod metadata;
 mod parse_cli;
 mod parse_cli_command;
 mod pcommand;
 mod progress;
 mod progress_update;
+mod relation;
 mod scripts;
 mod storage;
 mod wacommand; // w(ithout)a(pplication)command
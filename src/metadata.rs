@@ -0,0 +1,66 @@
+//! Here is extraction of metadata (title, authors, page count, etc.) from document files.
+//!
+//! Supported formats: PDF and EPUB. Everything is read locally from the file itself. OPF files
+//! that Calibre keeps next to books are read by `read_opf_metadata`.
+
+use std::path::Path;
+
+use thiserror::Error;
+
+mod epub_metadata;
+mod opf_metadata;
+mod pdf_metadata;
+
+pub use epub_metadata::EpubMetadataError;
+pub use opf_metadata::read_opf_metadata;
+pub use pdf_metadata::PdfMetadataError;
+
+const PDF_EXTENSION: &str = "pdf";
+const EPUB_EXTENSION: &str = "epub";
+
+#[derive(Debug, Error)]
+pub enum MetadataError {
+    #[error("pdf: {0}")]
+    Pdf(#[from] PdfMetadataError),
+    #[error("epub: {0}")]
+    Epub(#[from] EpubMetadataError),
+}
+
+/// Contains metadata extracted from a document file. Each field is optional because documents
+/// don't have to carry it.
+#[derive(Debug, Clone, Default)]
+pub struct Metadata {
+    pub title: Option<String>,
+    pub authors: Vec<String>,
+    /// Count of pages for PDF and count of spine items (chapters) for EPUB.
+    pub page_count: Option<usize>,
+    /// Plain text, markup is removed.
+    pub description: Option<String>,
+    /// Subjects of the document as they're written in it, they aren't valid tags yet.
+    pub tags: Vec<String>,
+}
+
+/// Returns `Ok(None)` if the format of the file isn't supported.
+pub fn extract_metadata(path: &Path) -> Result<Option<Metadata>, MetadataError> {
+    let extension = path
+        .extension()
+        .map(|t| t.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        PDF_EXTENSION => Ok(Some(pdf_metadata::read_pdf_metadata(path)?)),
+        EPUB_EXTENSION => Ok(Some(epub_metadata::read_epub_metadata(path)?)),
+        _ => Ok(None),
+    }
+}
+
+/// Returns `None` if the string is empty or consists of whitespaces only.
+fn non_empty_trimmed(string: &str) -> Option<String> {
+    let trimmed = string.trim();
+
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
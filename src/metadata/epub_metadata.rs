@@ -0,0 +1,62 @@
+use super::opf_metadata::{read_opf_metadata, OpfMetadataError};
+use super::Metadata;
+
+use std::fs::File;
+use std::io::{Error as IoError, Read};
+use std::path::Path;
+
+use roxmltree::{Document as XmlDocument, Error as XmlError};
+use thiserror::Error;
+use zip::result::ZipError;
+use zip::ZipArchive;
+
+const CONTAINER_PATH: &str = "META-INF/container.xml";
+const ROOTFILE_TAG: &str = "rootfile";
+const FULL_PATH_ATTRIBUTE: &str = "full-path";
+
+#[derive(Debug, Error)]
+pub enum EpubMetadataError {
+    #[error("couldn't read the epub archive: {0}")]
+    Zip(#[from] ZipError),
+    #[error("couldn't parse the container file (xml): {0}")]
+    Xml(#[from] XmlError),
+    #[error("the container file doesn't point to a package document")]
+    PackageDocumentNotSpecified,
+    #[error("package document: {0}")]
+    Opf(#[from] OpfMetadataError),
+    #[error("an I/O error occured: {0}")]
+    IO(#[from] IoError),
+}
+
+fn read_archived_file(
+    archive: &mut ZipArchive<File>,
+    name: &str,
+) -> Result<String, EpubMetadataError> {
+    let mut archived_file = archive.by_name(name)?;
+    let mut content = String::new();
+    archived_file.read_to_string(&mut content)?;
+
+    Ok(content)
+}
+
+/// Returns path (inside of the archive) of the package document (OPF file).
+fn package_document_path(container_content: &str) -> Result<String, EpubMetadataError> {
+    let container = XmlDocument::parse(container_content)?;
+
+    container
+        .descendants()
+        .filter(|node| node.is_element() && node.tag_name().name() == ROOTFILE_TAG)
+        .find_map(|rootfile| rootfile.attribute(FULL_PATH_ATTRIBUTE))
+        .map(|full_path| full_path.to_string())
+        .ok_or(EpubMetadataError::PackageDocumentNotSpecified)
+}
+
+pub fn read_epub_metadata(path: &Path) -> Result<Metadata, EpubMetadataError> {
+    let mut archive = ZipArchive::new(File::open(path)?)?;
+
+    let container_content = read_archived_file(&mut archive, CONTAINER_PATH)?;
+    let package_document_path = package_document_path(&container_content)?;
+    let package_document_content = read_archived_file(&mut archive, &package_document_path)?;
+
+    Ok(read_opf_metadata(&package_document_content)?)
+}
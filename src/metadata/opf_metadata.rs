@@ -0,0 +1,121 @@
+use super::{non_empty_trimmed, Metadata};
+
+use roxmltree::{Document as XmlDocument, Error as XmlError, Node, ParsingOptions};
+use thiserror::Error;
+
+const METADATA_TAG: &str = "metadata";
+const TITLE_TAG: &str = "title";
+const CREATOR_TAG: &str = "creator";
+const DESCRIPTION_TAG: &str = "description";
+const SUBJECT_TAG: &str = "subject";
+const SPINE_TAG: &str = "spine";
+const ITEMREF_TAG: &str = "itemref";
+
+#[derive(Debug, Error)]
+pub enum OpfMetadataError {
+    #[error("couldn't parse the package document (xml): {0}")]
+    Xml(#[from] XmlError),
+    #[error("the package document doesn't contain <{0}> element")]
+    MissingElement(String),
+}
+
+/// Returns children of the node with the given tag name. Namespaces are ignored because
+/// different writers use different prefixes for the same elements.
+fn children_with_tag<'a, 'input>(
+    node: Node<'a, 'input>,
+    tag: &'static str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children()
+        .filter(move |child| child.is_element() && child.tag_name().name() == tag)
+}
+
+fn node_text(node: Node) -> Option<String> {
+    let text: String = node
+        .descendants()
+        .filter(|descendant| descendant.is_text())
+        .filter_map(|descendant| descendant.text())
+        .collect();
+
+    non_empty_trimmed(&text)
+}
+
+/// HTML entities that are decoded in descriptions. `&amp;` must go last, otherwise `&amp;lt;`
+/// would become `<`.
+const HTML_ENTITIES: [(&str, &str); 6] = [
+    ("&lt;", "<"),
+    ("&gt;", ">"),
+    ("&quot;", "\""),
+    ("&#39;", "'"),
+    ("&nbsp;", " "),
+    ("&amp;", "&"),
+];
+
+/// Removes HTML markup from the text. Calibre keeps descriptions as escaped HTML, so after
+/// parsing of XML they still contain tags and entities.
+fn strip_markup(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut inside_tag = false;
+
+    for symbol in text.chars() {
+        match symbol {
+            '<' => inside_tag = true,
+            '>' if inside_tag => {
+                inside_tag = false;
+                // Tags usually separate paragraphs and lines.
+                stripped.push(' ');
+            }
+            symbol if !inside_tag => stripped.push(symbol),
+            _ => (),
+        }
+    }
+
+    let decoded = HTML_ENTITIES
+        .iter()
+        .fold(stripped, |text, (entity, symbol)| {
+            text.replace(entity, symbol)
+        });
+
+    decoded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Reads metadata from the content of an OPF (Open Packaging Format) file. This file is a package
+/// document of EPUB and also is used by Calibre to keep the metadata of books.
+pub fn read_opf_metadata(content: &str) -> Result<Metadata, OpfMetadataError> {
+    let options = ParsingOptions {
+        allow_dtd: true,
+        ..ParsingOptions::default()
+    };
+    let document = XmlDocument::parse_with_options(content, options)?;
+    let package = document.root_element();
+
+    let metadata = match children_with_tag(package, METADATA_TAG).next() {
+        Some(metadata) => metadata,
+        None => return Err(OpfMetadataError::MissingElement(METADATA_TAG.to_string())),
+    };
+
+    let title = children_with_tag(metadata, TITLE_TAG)
+        .filter_map(node_text)
+        .next();
+    let authors = children_with_tag(metadata, CREATOR_TAG)
+        .filter_map(node_text)
+        .collect();
+    let page_count = children_with_tag(package, SPINE_TAG)
+        .next()
+        .map(|spine| children_with_tag(spine, ITEMREF_TAG).count())
+        .filter(|&count| count > 0);
+    let description = children_with_tag(metadata, DESCRIPTION_TAG)
+        .filter_map(node_text)
+        .map(|description| strip_markup(&description))
+        .find(|description| !description.is_empty());
+    let tags = children_with_tag(metadata, SUBJECT_TAG)
+        .filter_map(node_text)
+        .collect();
+
+    Ok(Metadata {
+        title,
+        authors,
+        page_count,
+        description,
+        tags,
+    })
+}
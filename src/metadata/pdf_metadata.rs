@@ -0,0 +1,73 @@
+use super::{non_empty_trimmed, Metadata};
+
+use std::path::Path;
+
+use lopdf::{decode_text_string, Dictionary, Document as PdfDocument, Error as LopdfError};
+use thiserror::Error;
+
+const INFO_KEY: &[u8] = b"Info";
+const TITLE_KEY: &[u8] = b"Title";
+const AUTHOR_KEY: &[u8] = b"Author";
+
+/// Separators used between several authors in the `Author` field.
+const AUTHOR_SEPARATORS: [&str; 2] = [";", " and "];
+
+#[derive(Debug, Error)]
+pub enum PdfMetadataError {
+    #[error("couldn't read the pdf document: {0}")]
+    Lopdf(#[from] LopdfError),
+}
+
+fn read_text_field(info: &Dictionary, key: &[u8]) -> Option<String> {
+    let object = info.get(key).ok()?;
+    let text = decode_text_string(object).ok()?;
+
+    non_empty_trimmed(&text)
+}
+
+fn split_authors(stringified_authors: &str) -> Vec<String> {
+    let mut authors = vec![stringified_authors.to_string()];
+
+    for separator in AUTHOR_SEPARATORS {
+        authors = authors
+            .iter()
+            .flat_map(|author| author.split(separator))
+            .map(|author| author.to_string())
+            .collect();
+    }
+
+    authors
+        .iter()
+        .filter_map(|author| non_empty_trimmed(author))
+        .collect()
+}
+
+pub fn read_pdf_metadata(path: &Path) -> Result<Metadata, PdfMetadataError> {
+    let document = PdfDocument::load(path)?;
+
+    let page_count = match document.get_pages().len() {
+        0 => None,
+        page_count => Some(page_count),
+    };
+
+    // The information dictionary is optional, so its absence isn't an error.
+    let info = document
+        .trailer
+        .get(INFO_KEY)
+        .and_then(|info| document.dereference(info))
+        .and_then(|(_, info)| info.as_dict())
+        .ok();
+
+    let title = info.and_then(|info| read_text_field(info, TITLE_KEY));
+    let authors = info
+        .and_then(|info| read_text_field(info, AUTHOR_KEY))
+        .map(|authors| split_authors(&authors))
+        .unwrap_or_default();
+
+    Ok(Metadata {
+        title,
+        authors,
+        page_count,
+        ..Default::default()
+    })
+}
@@ -1,12 +1,15 @@
 use crate::comps_appearance::{progress_from_string, progress_update_from_string};
+use crate::entity_selector::EntitySelector;
 use crate::id::ID;
 use crate::progress::Progress;
 use crate::progress_update::ProgressUpdate;
 
 use std::path::PathBuf;
 
-use clap::{ArgAction, Parser, Subcommand};
+use clap::error::ErrorKind as ClapErrorKind;
+use clap::{ArgAction, ArgGroup, Command as ClapCommand, CommandFactory, Parser, Subcommand};
 
+/// Library entities are selected by path, `#<id>` or a unique name (or its prefix).
 #[derive(Debug, Parser)]
 #[command(
     name = env!("CARGO_PKG_NAME"),
@@ -19,6 +22,25 @@ pub struct CLI {
     pub command: CliCommand,
 }
 
+/// Exits with the error of clap for the unknown subcommand, so similar subcommands are suggested
+/// as if commands of the scripts file weren't accepted.
+pub fn exit_unknown_subcommand(name: &str) -> ! {
+    // The derived command always accepts external subcommands, so its copy without them parses
+    // the name again.
+    let mut command = ClapCommand::new(env!("CARGO_PKG_NAME"))
+        .subcommands(CLI::command().get_subcommands().cloned())
+        .subcommand_required(true);
+    match command.try_get_matches_from_mut([env!("CARGO_PKG_NAME"), name]) {
+        Err(err) => err.exit(),
+        Ok(_) => command
+            .error(
+                ClapErrorKind::InvalidSubcommand,
+                format!("unrecognized subcommand '{}'", name),
+            )
+            .exit(),
+    }
+}
+
 #[derive(Debug, Subcommand)]
 // 'llc' prefix is 'Low-Level Command'.
 pub enum CliCommand {
@@ -56,15 +78,15 @@ pub enum CliCommand {
     /// Delete the description from the stroage of current directory
     #[command(name = "llc_del_description")]
     DelDescription { id: ID },
-    /// Return ID of the library entity associated with the given path
+    /// Return ID of the selected library entity
     #[command(name = "get_id")]
-    GetId { path: PathBuf },
-    /// Return progress of the library entity associated wtih the given ID
+    GetId { entity: EntitySelector },
+    /// Return progress of the selected library entity
     #[command(name = "get_progress")]
-    GetProgress { id: ID },
-    /// Return base of the library entity associated wtih the given ID
+    GetProgress { entity: EntitySelector },
+    /// Return base of the selected library entity
     #[command(name = "get_entitybase")]
-    GetEntitybase { id: ID },
+    GetEntitybase { entity: EntitySelector },
     /// Add library entity to the storage of current directory
     #[command(name = "add_libentity")]
     AddLibentity {
@@ -76,18 +98,55 @@ pub enum CliCommand {
         #[arg(long, short = 'c')]
         prog_ceil: Option<usize>,
     },
-    /// Delete library entity associated with the given path
+    /// Add untracked files to the storage of current directory. Names and progress ceilings are
+    /// taken from metadata of files, tags are taken from the parent section
+    #[command(name = "add")]
+    Add {
+        path: PathBuf,
+        /// Add all untracked files beneath the given directory
+        #[arg(long, short = 'r', action = ArgAction::SetTrue)]
+        recursive: bool,
+        /// Don't ask for confirmation, skip files whose attributes can't be defined
+        #[arg(long, short = 'y', action = ArgAction::SetTrue)]
+        yes: bool,
+        #[arg(long = "hidden", action = ArgAction::SetTrue)]
+        show_hidden: bool,
+    },
+    /// Delete the selected library entity
     #[command(name = "del_libentity")]
-    DelLibentity { path: PathBuf },
-    /// Returns the "cover" of the library entity associated with the given path
+    DelLibentity { entity: EntitySelector },
+    /// Edit name, tags, description and progress ceiling of the selected library entity in
+    /// $EDITOR
+    #[command(name = "edit")]
+    Edit { entity: EntitySelector },
+    /// Open the description of the selected library entity in $EDITOR as a Markdown note
+    #[command(name = "note")]
+    Note {
+        entity: EntitySelector,
+        /// Append the text to the note as a new paragraph instead of opening the editor
+        #[arg(long, short = 'a')]
+        append: Option<String>,
+    },
+    /// Show or change bibliography (authors, year, publisher, etc) of the selected library entity
+    ///
+    /// Fields are changed by assignments like 'year=1999'. Authors are separated by ';', empty
+    /// value unsets the field
+    #[command(name = "bib")]
+    Bib {
+        entity: EntitySelector,
+        /// Fields: authors, year, publisher, isbn, doi, language, edition, series, series_index
+        #[arg(value_name = "FIELD=VALUE")]
+        assignments: Vec<String>,
+    },
+    /// Returns the "cover" of the selected library entity
     #[command(name = "look")]
-    Look { path: PathBuf },
-    /// Open the library entity associated with the given path
+    Look { entity: EntitySelector },
+    /// Open the selected library entity
     ///
     /// The opening method is dictated in the configuration
     #[command(name = "open")]
     Open {
-        path: PathBuf,
+        entity: EntitySelector,
         #[arg(long = "just_look", short = 'j', action = ArgAction::SetTrue)]
         just_look: bool,
     },
@@ -96,6 +155,99 @@ pub enum CliCommand {
     List {
         #[arg(long, short = 'w', action = ArgAction::SetTrue)]
         wide: bool,
+        /// Show only library entities having the tag or its descendant. May be repeated
+        #[arg(long = "tag", short = 't')]
+        tags: Vec<String>,
+        /// Show only library entities whose name or bibliography contains the text
+        #[arg(long, short = 's')]
+        search: Option<String>,
+        /// Show only library entities passing the filter 'filters.<name>' of the scripts file.
+        /// May be repeated
+        #[arg(long = "filter", value_name = "NAME")]
+        filters: Vec<String>,
+        /// Sort library entities by the sorter 'sorters.<name>' of the scripts file
+        #[arg(long, value_name = "NAME")]
+        sort: Option<String>,
+    },
+    /// Print BibTeX entries of the library entities
+    #[command(name = "export")]
+    Export {
+        /// Export in the BibTeX format. It's the only supported format yet
+        #[arg(long, action = ArgAction::SetTrue, required = true)]
+        bibtex: bool,
+        /// Export only library entities having the tag or its descendant. May be repeated
+        #[arg(long = "tag", short = 't')]
+        tags: Vec<String>,
+        /// Export only library entities whose name or bibliography contains the text
+        #[arg(long, short = 's')]
+        search: Option<String>,
+    },
+    /// Import bibliographies from BibTeX file or books from Calibre library
+    #[command(name = "import")]
+    #[command(group(ArgGroup::new("source").required(true).args(["bibtex", "calibre"])))]
+    Import {
+        /// Fill bibliographies of the library entities from the BibTeX file. Entries are matched
+        /// with library entities by the 'file' field or by the title
+        #[arg(long, value_name = "FILE")]
+        bibtex: Option<PathBuf>,
+        /// Add books of the Calibre library. The Calibre library must be inside of the library
+        #[arg(long, value_name = "DIR")]
+        calibre: Option<PathBuf>,
+    },
+    /// Run the command defined in the scripts file as 'commands.<name>'
+    ///
+    /// Commands of the scripts file may also be run as 'popusk <name> [args]'
+    #[command(name = "run")]
+    Run {
+        name: String,
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Commands of the scripts file called by their names
+    #[command(external_subcommand)]
+    External(Vec<String>),
+    /// Print library entities nested under their parent sections
+    ///
+    /// Directories that aren't added as sections are shown as untracked
+    #[command(name = "tree")]
+    Tree {
+        /// Print only library entities beneath the section
+        section: Option<EntitySelector>,
+    },
+    /// Record that the first library entity relates to the second one, e.g. 'popusk link
+    /// ru.pdf translation_of en.pdf'
+    ///
+    /// Suggested relations: translation_of, edition_of, solutions_for, cites, part_of
+    #[command(name = "link")]
+    Link {
+        source: EntitySelector,
+        relation: String,
+        target: EntitySelector,
+    },
+    /// Remove the relation recorded by 'link'
+    #[command(name = "unlink")]
+    Unlink {
+        source: EntitySelector,
+        relation: String,
+        target: EntitySelector,
+    },
+    /// Work with the config and the scripts file
+    #[command(name = "config")]
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Work with the scripts file
+    #[command(name = "scripts")]
+    Scripts {
+        #[command(subcommand)]
+        command: ScriptsCommand,
+    },
+    /// Manage collections: ordered lists of library entities independent of directories
+    #[command(name = "collection")]
+    Collection {
+        #[command(subcommand)]
+        command: CollectionCommand,
     },
     /// Return status of current directory: untracked files for example
     #[command(name = "status")]
@@ -105,17 +257,108 @@ pub enum CliCommand {
         #[arg(long = "ignore")]
         ignore: Option<String>,
     },
-    /// Change progress associated with the given ID
+    /// Change progress of the selected library entity
     #[command(name = "change_progress")]
     ChangeProgress {
-        id: ID,
+        entity: EntitySelector,
         #[arg(value_parser = progress_update_from_string)]
         progress_update: ProgressUpdate,
     },
-    /// Extend current set of tags by new ones
+    /// Extend current set of tags of the selected library entity by new ones
     #[command(name = "add_tags")]
-    AddTags { id: ID, tags: String },
-    /// Delete tags associated with the given ID
+    AddTags {
+        entity: EntitySelector,
+        tags: String,
+    },
+    /// Delete tags by names or glob patterns (e.g. 'draft-*'). Without patterns tags are picked
+    /// interactively
+    ///
+    /// The first argument selects the library entity, the others are patterns. If filter
+    /// tags are given, tags are deleted from all library entities matching them and all arguments
+    /// are patterns
     #[command(name = "del_tags")]
-    DelTags { id: ID },
+    DelTags {
+        args: Vec<String>,
+        /// Delete tags from library entities having the tag or its descendant. May be repeated
+        #[arg(long = "filter", short = 'f')]
+        filters: Vec<String>,
+    },
+    /// Return all tags of the library with counts of library entities having them
+    #[command(name = "tags")]
+    Tags {
+        /// Show hierarchical tags as a tree. Each tag counts library entities of its subtree
+        #[arg(long, action = ArgAction::SetTrue)]
+        tree: bool,
+        #[command(subcommand)]
+        command: Option<TagsCommand>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TagsCommand {
+    /// Rename the tag and its descendants in all library entities
+    #[command(name = "rename")]
+    Rename { old_tag: String, new_tag: String },
+    /// Replace the first tag by the second one in all library entities, descendants of the first
+    /// tag are moved under the second one
+    #[command(name = "merge")]
+    Merge {
+        source_tag: String,
+        target_tag: String,
+    },
+    /// Remove the tag and its descendants from all library entities
+    #[command(name = "rm")]
+    Rm { tag: String },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CollectionCommand {
+    /// Create an empty collection
+    #[command(name = "create")]
+    Create { name: String },
+    /// Add the library entities to the collection in the given order
+    #[command(name = "add")]
+    Add {
+        name: String,
+        #[arg(required = true)]
+        entities: Vec<EntitySelector>,
+        /// Insert the library entities at the position (counted from 1) instead of appending them
+        #[arg(long, short = 'p')]
+        position: Option<usize>,
+    },
+    /// Remove the library entities from the collection. They stay in the library
+    #[command(name = "rm")]
+    Rm {
+        name: String,
+        #[arg(required = true)]
+        entities: Vec<EntitySelector>,
+    },
+    /// Delete the collection. Its library entities stay in the library
+    #[command(name = "delete")]
+    Delete { name: String },
+    /// Print library entities of the collection in order or, if no name is given, all collections
+    #[command(name = "show")]
+    Show { name: Option<String> },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ScriptsCommand {
+    /// Load the scripts file and call its functions with sample data
+    ///
+    /// Hooks are called without changing the library, commands are only checked to be functions
+    #[command(name = "check")]
+    Check,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Write the default config and scripts file to $HOME/.config/popusk for customization
+    ///
+    /// They're used when the files don't exist, so it's needed only for changing them
+    #[command(name = "init")]
+    Init {
+        /// Overwrite existing files
+        #[arg(long, action = ArgAction::SetTrue)]
+        force: bool,
+    },
 }
@@ -1,4 +1,5 @@
-use crate::parse_cli::CliCommand;
+use crate::libentity_filter::LibEntityFilter;
+use crate::parse_cli::{CliCommand, CollectionCommand, ConfigCommand, ScriptsCommand, TagsCommand};
 use crate::pcommand::*;
 use crate::wacommand::*;
 
@@ -19,8 +20,8 @@ pub fn parse_cli_command(cli_command: CliCommand) -> P_WA_Command {
         CliCommand::AddEntitybase { id } => {
             P_WA_Command::PCommand(Box::new(AddEntitybasePCMD::new(id)))
         }
-        CliCommand::AddTags { id, tags } => {
-            P_WA_Command::PCommand(Box::new(AddTagsPCMD::new(id, tags)))
+        CliCommand::AddTags { entity, tags } => {
+            P_WA_Command::PCommand(Box::new(AddTagsPCMD::new(entity, tags)))
         }
         CliCommand::AddDescription { id, description } => {
             P_WA_Command::PCommand(Box::new(AddDescriptionPCMD::new(id, description)))
@@ -32,16 +33,18 @@ pub fn parse_cli_command(cli_command: CliCommand) -> P_WA_Command {
         CliCommand::DelEntitybase { id } => {
             P_WA_Command::PCommand(Box::new(DelEntitybasePCMD::new(id)))
         }
-        CliCommand::DelTags { id } => P_WA_Command::PCommand(Box::new(DelTagsPCMD::new(id))),
+        CliCommand::DelTags { args, filters } => {
+            P_WA_Command::PCommand(Box::new(DelTagsPCMD::new(args, filters)))
+        }
         CliCommand::DelDescription { id } => {
             P_WA_Command::PCommand(Box::new(DelDescriptionPCMD::new(id)))
         }
-        CliCommand::GetId { path } => P_WA_Command::PCommand(Box::new(GetIDPCMD::new(path))),
-        CliCommand::GetProgress { id } => {
-            P_WA_Command::PCommand(Box::new(GetProgressPCMD::new(id)))
+        CliCommand::GetId { entity } => P_WA_Command::PCommand(Box::new(GetIDPCMD::new(entity))),
+        CliCommand::GetProgress { entity } => {
+            P_WA_Command::PCommand(Box::new(GetProgressPCMD::new(entity)))
         }
-        CliCommand::GetEntitybase { id } => {
-            P_WA_Command::PCommand(Box::new(GetEntitybasePCMD::new(id)))
+        CliCommand::GetEntitybase { entity } => {
+            P_WA_Command::PCommand(Box::new(GetEntitybasePCMD::new(entity)))
         }
         CliCommand::AddLibentity {
             path,
@@ -49,23 +52,121 @@ pub fn parse_cli_command(cli_command: CliCommand) -> P_WA_Command {
             tags,
             prog_ceil,
         } => P_WA_Command::PCommand(Box::new(AddLibentityPCMD::new(path, name, tags, prog_ceil))),
-        CliCommand::DelLibentity { path } => {
-            P_WA_Command::PCommand(Box::new(DelLibentityPCMD::new(path)))
+        CliCommand::Add {
+            path,
+            recursive,
+            yes,
+            show_hidden,
+        } => P_WA_Command::PCommand(Box::new(AddPCMD::new(path, recursive, yes, show_hidden))),
+        CliCommand::DelLibentity { entity } => {
+            P_WA_Command::PCommand(Box::new(DelLibentityPCMD::new(entity)))
         }
-        CliCommand::Look { path } => P_WA_Command::PCommand(Box::new(LookPCMD::new(path))),
-        CliCommand::List { wide } => {
-            P_WA_Command::PCommand(Box::new(ListPCMD::new(ListMode::wide(wide))))
+        CliCommand::Edit { entity } => P_WA_Command::PCommand(Box::new(EditPCMD::new(entity))),
+        CliCommand::Note { entity, append } => {
+            P_WA_Command::PCommand(Box::new(NotePCMD::new(entity, append)))
         }
+        CliCommand::Bib {
+            entity,
+            assignments,
+        } => P_WA_Command::PCommand(Box::new(BibPCMD::new(entity, assignments))),
+        CliCommand::Look { entity } => P_WA_Command::PCommand(Box::new(LookPCMD::new(entity))),
+        CliCommand::List {
+            wide,
+            tags,
+            search,
+            filters,
+            sort,
+        } => P_WA_Command::PCommand(Box::new(ListPCMD::new(
+            ListMode::wide(wide),
+            LibEntityFilter::new(tags, search),
+            filters,
+            sort,
+        ))),
+        CliCommand::Export {
+            bibtex: _,
+            tags,
+            search,
+        } => P_WA_Command::PCommand(Box::new(ExportBibtexPCMD::new(LibEntityFilter::new(
+            tags, search,
+        )))),
+        CliCommand::Import { bibtex, calibre } => match (bibtex, calibre) {
+            (Some(bibtex), _) => P_WA_Command::PCommand(Box::new(ImportBibtexPCMD::new(bibtex))),
+            (None, Some(calibre)) => {
+                P_WA_Command::PCommand(Box::new(ImportCalibrePCMD::new(calibre)))
+            }
+            (None, None) => unreachable!(), // Cause clap requires one of the sources.
+        },
         CliCommand::Status {
             show_hidden,
             ignore,
         } => P_WA_Command::PCommand(Box::new(StatusPCMD::new(show_hidden, ignore))),
-        CliCommand::Open { path, just_look } => {
-            P_WA_Command::PCommand(Box::new(OpenPCMD::new(path, just_look)))
+        CliCommand::Open { entity, just_look } => {
+            P_WA_Command::PCommand(Box::new(OpenPCMD::new(entity, just_look)))
         }
         CliCommand::ChangeProgress {
-            id,
+            entity,
             progress_update,
-        } => P_WA_Command::PCommand(Box::new(ChangeProgressPCMD::new(id, progress_update))),
+        } => P_WA_Command::PCommand(Box::new(ChangeProgressPCMD::new(entity, progress_update))),
+        CliCommand::Tags { tree, command } => match command {
+            None => P_WA_Command::PCommand(Box::new(TagsPCMD::new(tree))),
+            Some(TagsCommand::Rename { old_tag, new_tag }) => {
+                P_WA_Command::PCommand(Box::new(RenameTagPCMD::new(old_tag, new_tag)))
+            }
+            Some(TagsCommand::Merge {
+                source_tag,
+                target_tag,
+            }) => P_WA_Command::PCommand(Box::new(MergeTagsPCMD::new(source_tag, target_tag))),
+            Some(TagsCommand::Rm { tag }) => {
+                P_WA_Command::PCommand(Box::new(RemoveTagPCMD::new(tag)))
+            }
+        },
+        CliCommand::Run { name, args } => {
+            P_WA_Command::PCommand(Box::new(RunPCMD::new(name, args)))
+        }
+        CliCommand::External(mut args) => {
+            // Clap gives the name of the subcommand as the first element.
+            let name = args.remove(0);
+            P_WA_Command::WACommand(Box::new(ExternalWACMD::new(name, args)))
+        }
+        CliCommand::Tree { section } => P_WA_Command::PCommand(Box::new(TreePCMD::new(section))),
+        CliCommand::Link {
+            source,
+            relation,
+            target,
+        } => P_WA_Command::PCommand(Box::new(LinkPCMD::new(source, relation, target))),
+        CliCommand::Unlink {
+            source,
+            relation,
+            target,
+        } => P_WA_Command::PCommand(Box::new(UnlinkPCMD::new(source, relation, target))),
+        CliCommand::Config { command } => match command {
+            ConfigCommand::Init { force } => {
+                P_WA_Command::WACommand(Box::new(ConfigInitWACMD::new(force)))
+            }
+        },
+        CliCommand::Scripts { command } => match command {
+            ScriptsCommand::Check => P_WA_Command::WACommand(Box::new(ScriptsCheckWACMD::new())),
+        },
+        CliCommand::Collection { command } => match command {
+            CollectionCommand::Create { name } => {
+                P_WA_Command::PCommand(Box::new(CreateCollectionPCMD::new(name)))
+            }
+            CollectionCommand::Add {
+                name,
+                entities,
+                position,
+            } => {
+                P_WA_Command::PCommand(Box::new(AddToCollectionPCMD::new(name, entities, position)))
+            }
+            CollectionCommand::Rm { name, entities } => {
+                P_WA_Command::PCommand(Box::new(RemoveFromCollectionPCMD::new(name, entities)))
+            }
+            CollectionCommand::Delete { name } => {
+                P_WA_Command::PCommand(Box::new(DeleteCollectionPCMD::new(name)))
+            }
+            CollectionCommand::Show { name } => {
+                P_WA_Command::PCommand(Box::new(ShowCollectionPCMD::new(name)))
+            }
+        },
     }
 }
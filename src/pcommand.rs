@@ -12,8 +12,11 @@
 
 use crate::app::{App, AppError};
 use crate::core_commands::CoreError;
+use crate::editor::EditorError;
+use crate::entity_selector::EntitySelectorError;
 use crate::error_ext::ComError;
 use crate::library::LibraryError;
+use crate::library_walker::LibraryWalkerError;
 use crate::progress_update::ProgressUpdateError;
 use crate::scripts::ScriptsError;
 use crate::storage::StorageError;
@@ -27,43 +30,84 @@ mod add_description_pcmd;
 mod add_entitybase_pcmd;
 mod add_libentity_pcmd;
 mod add_path_pcmd;
+mod add_pcmd;
 mod add_progress_pcmd;
 mod add_tags_pcmd;
+mod add_to_collection_pcmd;
+mod bib_pcmd;
 mod change_progress_pcmd;
+mod create_collection_pcmd;
 mod del_description_pcmd;
 mod del_entitybase_pcmd;
 mod del_libentity_pcmd;
 mod del_path_pcmd;
 mod del_progress_pcmd;
 mod del_tags_pcmd;
+mod delete_collection_pcmd;
+mod edit_pcmd;
+mod export_bibtex_pcmd;
 mod get_entitybase_pcmd;
 mod get_id_pcmd;
 mod get_progress_pcmd;
+mod hooks;
+mod import_bibtex_pcmd;
+mod import_calibre_pcmd;
+mod link_pcmd;
 mod list_pcmd;
 mod look_pcmd;
+mod merge_tags_pcmd;
+mod note_pcmd;
 mod open_pcmd;
+mod remove_from_collection_pcmd;
+mod remove_tag_pcmd;
+mod rename_tag_pcmd;
+mod run_pcmd;
+mod show_collection_pcmd;
 mod status_pcmd;
+mod tags_pcmd;
+mod tree_pcmd;
+mod unlink_pcmd;
 
 pub use add_description_pcmd::*;
 pub use add_entitybase_pcmd::*;
 pub use add_libentity_pcmd::*;
 pub use add_path_pcmd::*;
+pub use add_pcmd::*;
 pub use add_progress_pcmd::*;
 pub use add_tags_pcmd::*;
+pub use add_to_collection_pcmd::*;
+pub use bib_pcmd::*;
 pub use change_progress_pcmd::*;
+pub use create_collection_pcmd::*;
 pub use del_description_pcmd::*;
 pub use del_entitybase_pcmd::*;
 pub use del_libentity_pcmd::*;
 pub use del_path_pcmd::*;
 pub use del_progress_pcmd::*;
 pub use del_tags_pcmd::*;
+pub use delete_collection_pcmd::*;
+pub use edit_pcmd::*;
+pub use export_bibtex_pcmd::*;
 pub use get_entitybase_pcmd::*;
 pub use get_id_pcmd::*;
 pub use get_progress_pcmd::*;
+pub use import_bibtex_pcmd::*;
+pub use import_calibre_pcmd::*;
+pub use link_pcmd::*;
 pub use list_pcmd::*;
 pub use look_pcmd::*;
+pub use merge_tags_pcmd::*;
+pub use note_pcmd::*;
 pub use open_pcmd::*;
+pub use remove_from_collection_pcmd::*;
+pub use remove_tag_pcmd::*;
+pub use rename_tag_pcmd::*;
+pub use run_pcmd::*;
+pub use show_collection_pcmd::*;
 pub use status_pcmd::*;
+pub use tags_pcmd::*;
+pub use tree_pcmd::*;
+pub use unlink_pcmd::*;
 
 #[derive(Debug, Error)]
 pub enum PExecutionError {
@@ -83,6 +127,12 @@ pub enum PExecutionError {
     ProgressUpdateError(#[from] ProgressUpdateError),
     #[error("library error: {0}")]
     LibraryError(#[from] LibraryError),
+    #[error("library walker error: {0}")]
+    LibraryWalkerError(#[from] LibraryWalkerError),
+    #[error("editor error: {0}")]
+    EditorError(#[from] EditorError),
+    #[error("{0}")]
+    EntitySelectorError(#[from] EntitySelectorError),
 
     #[error("{0}")]
     Other(#[from] ComError),
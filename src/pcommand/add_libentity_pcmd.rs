@@ -1,15 +1,77 @@
 use crate::app::App;
+use crate::bibliography::Bibliography;
 use crate::comps_appearance::parse_string_to_tags;
 use crate::comps_interaction::libentity_has_progress;
 use crate::entity_base::{EntityType, Tag};
 use crate::error_ext::ComResult;
 use crate::libentity::LibEntityData;
+use crate::metadata::{extract_metadata, Metadata};
 use crate::progress::Progress;
 
+use super::hooks::libentity_added;
 use super::{PCommand, PExecutionError};
 
 use std::io::{stdin, stdout, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Defines entity type of the file by its kind and extension. Extensions of documents are taken
+/// from the config.
+pub(super) fn etype_of_path(path: &Path, app: &App) -> ComResult<EntityType> {
+    if !path.exists() {
+        return Err(format!("the file doesn't exist: {}", path.to_string_lossy()).into());
+    }
+
+    if path.is_dir() {
+        Ok(EntityType::Section)
+    } else if path.is_file() {
+        let extension = path
+            .extension()
+            .map(|t| t.to_string_lossy().to_string())
+            .unwrap_or_else(|| "".to_string());
+
+        if app.config()?.document_extension().contains(&extension) {
+            Ok(EntityType::Document)
+        } else {
+            Ok(EntityType::Regular)
+        }
+    } else {
+        Ok(EntityType::Regular)
+    }
+}
+
+/// Returns `None` if the format of the file isn't supported or the metadata couldn't be extracted.
+/// Extraction errors are only reported because the metadata is optional.
+pub(super) fn metadata_of_path(path: &Path) -> Option<Metadata> {
+    if !path.is_file() {
+        return None;
+    }
+
+    match extract_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            eprintln!(
+                "Couldn't extract metadata of '{}': {}",
+                path.to_string_lossy(),
+                err
+            );
+            None
+        }
+    }
+}
+
+/// Bibliography made of the metadata, `None` if the metadata has no bibliographic fields.
+pub(super) fn bibliography_of_metadata(metadata: &Metadata) -> Option<Bibliography> {
+    let bibliography = Bibliography {
+        authors: metadata.authors.clone(),
+        ..Default::default()
+    };
+
+    if bibliography.is_empty() {
+        None
+    } else {
+        Some(bibliography)
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct AddLibentityPCMD {
@@ -34,11 +96,21 @@ impl AddLibentityPCMD {
         }
     }
 
-    fn read_name(&self) -> ComResult<String> {
+    /// Missing attributes of the metadata are read from stdin.
+    fn read_metadata(&self) -> Option<Metadata> {
+        metadata_of_path(&self.path)
+    }
+
+    fn read_name(&self, metadata: Option<&Metadata>) -> ComResult<String> {
         if let Some(name) = &self.name {
             return Ok(name.clone());
         }
 
+        if let Some(title) = metadata.and_then(|metadata| metadata.title.as_ref()) {
+            println!("Name: {}", title);
+            return Ok(title.clone());
+        }
+
         let mut stdout = stdout();
         stdout.write_all(b"Name: ")?;
         stdout.flush()?;
@@ -64,11 +136,16 @@ impl AddLibentityPCMD {
         Ok(parse_string_to_tags(stringified_tags.trim())?)
     }
 
-    fn read_progceil(&self) -> ComResult<usize> {
+    fn read_progceil(&self, metadata: Option<&Metadata>) -> ComResult<usize> {
         if let Some(prog_ceil) = self.prog_ceil {
             return Ok(prog_ceil);
         }
 
+        if let Some(page_count) = metadata.and_then(|metadata| metadata.page_count) {
+            println!("Progress ceiling: {}", page_count);
+            return Ok(page_count);
+        }
+
         let mut stdout = stdout();
         stdout.write_all(b"Progress ceiling: ")?;
         stdout.flush()?;
@@ -82,27 +159,7 @@ impl AddLibentityPCMD {
     }
 
     fn read_etype(&self, app: &App) -> ComResult<EntityType> {
-        if !self.path.exists() {
-            return Err(format!("the file doesn't exist: {}", self.path.to_string_lossy()).into());
-        }
-
-        if self.path.is_dir() {
-            Ok(EntityType::Section)
-        } else if self.path.is_file() {
-            let extension = self
-                .path
-                .extension()
-                .map(|t| t.to_string_lossy().to_string())
-                .unwrap_or_else(|| "".to_string());
-
-            if app.config().document_extension().contains(&extension) {
-                Ok(EntityType::Document)
-            } else {
-                Ok(EntityType::Regular)
-            }
-        } else {
-            Ok(EntityType::Regular)
-        }
+        etype_of_path(&self.path, app)
     }
 
     fn read_description(&self) -> ComResult<Option<String>> {
@@ -124,19 +181,22 @@ impl AddLibentityPCMD {
 impl PCommand for AddLibentityPCMD {
     fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
         // The attributes must be defined in the start of the function.
-        let name = self.read_name()?;
+        let metadata = self.read_metadata();
+        let name = self.read_name(metadata.as_ref())?;
         let tags = self.read_tags()?;
         let etype = self.read_etype(app)?;
         let progress = if libentity_has_progress(etype) {
-            Some(Progress::new(self.read_progceil()?))
+            Some(Progress::new(self.read_progceil(metadata.as_ref())?))
         } else {
             None
         };
         let description = self.read_description()?;
+        let bibliography = metadata.as_ref().and_then(bibliography_of_metadata);
 
         let libentity_data = LibEntityData {
             path: self.path.clone(),
             description,
+            bibliography,
             etype,
             name,
             progress,
@@ -144,6 +204,7 @@ impl PCommand for AddLibentityPCMD {
         };
 
         app.library_mut().add_libentity(libentity_data)?;
+        libentity_added(app, self.path.clone());
 
         Ok(())
     }
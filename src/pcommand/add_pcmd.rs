@@ -0,0 +1,213 @@
+use crate::app::App;
+use crate::comps_appearance::{entitytype_to_string, tags_to_string};
+use crate::comps_interaction::libentity_has_progress;
+use crate::entity_base::{EntityType, Tag};
+use crate::libentity::LibEntityData;
+use crate::library::{Library, LibraryError};
+use crate::library_walker::LibraryWalker;
+use crate::progress::Progress;
+
+use super::add_libentity_pcmd::{bibliography_of_metadata, etype_of_path, metadata_of_path};
+use super::hooks::libentity_added;
+use super::{PCommand, PExecutionError};
+
+use std::io::{stdin, stdout, Error as IoError, Write};
+use std::path::{Path, PathBuf};
+
+fn read_input_stdin() -> Result<String, IoError> {
+    let mut string = String::new();
+    stdin().read_line(&mut string)?;
+
+    Ok(string.trim().to_string())
+}
+
+fn write_stdout(string: &str) -> Result<(), IoError> {
+    stdout().write_all(string.as_bytes())?;
+    stdout().flush()?;
+
+    Ok(())
+}
+
+/// Returns name of the file without extension or name of the directory.
+fn name_from_path(path: &Path) -> String {
+    let name = if path.is_dir() {
+        path.file_name()
+    } else {
+        path.file_stem()
+    };
+
+    name.map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
+
+/// Returns tags of the nearest tracked section containing the given path. If there's no such
+/// section, returns empty list.
+fn parent_section_tags(library: &Library, path: &Path) -> Result<Vec<Tag>, LibraryError> {
+    let ancestors = path
+        .ancestors()
+        .skip(1)
+        .take_while(|ancestor| ancestor.file_name().is_some());
+
+    for ancestor in ancestors {
+        if let Some(id) = library.get_id(ancestor.to_path_buf())? {
+            if let Some(base) = library.get_base(id)? {
+                if base.etype() == EntityType::Section {
+                    return Ok(base.tags().clone());
+                }
+            }
+        }
+    }
+
+    Ok(Vec::new())
+}
+
+#[derive(Debug, Clone)]
+enum AddOutcome {
+    Added,
+    Skipped { reason: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct AddPCMD {
+    path: PathBuf,
+    recursive: bool,
+    yes: bool,
+    walker: LibraryWalker,
+}
+
+impl AddPCMD {
+    pub fn new(path: PathBuf, recursive: bool, yes: bool, show_hidden: bool) -> Self {
+        AddPCMD {
+            path,
+            recursive,
+            yes,
+            walker: LibraryWalker::new(show_hidden, Vec::new()),
+        }
+    }
+
+    /// Asks user whether the file should be added. Always `true` in non-interactive mode.
+    fn confirm(
+        &self,
+        path: &Path,
+        etype: EntityType,
+        name: &str,
+        tags: &[Tag],
+    ) -> Result<bool, IoError> {
+        if self.yes {
+            return Ok(true);
+        }
+
+        write_stdout(&format!(
+            "Add '{}' as {} '{}' (tags: {})? [Y/n]: ",
+            path.to_string_lossy(),
+            entitytype_to_string(etype),
+            name,
+            tags_to_string(tags),
+        ))?;
+        let answer = read_input_stdin()?.to_lowercase();
+
+        Ok(answer.is_empty() || answer == "y" || answer == "yes")
+    }
+
+    /// Returns `None` if the ceiling is unknown and can't be asked (non-interactive mode) or user
+    /// left it empty.
+    fn read_progceil(&self, page_count: Option<usize>) -> Result<Option<usize>, PExecutionError> {
+        if page_count.is_some() || self.yes {
+            return Ok(page_count);
+        }
+
+        write_stdout("Progress ceiling (leave empty to skip the file): ")?;
+        let stringified_progceil = read_input_stdin()?;
+
+        if stringified_progceil.is_empty() {
+            return Ok(None);
+        }
+
+        match stringified_progceil.parse::<usize>() {
+            Ok(0) | Err(_) => Ok(None),
+            Ok(prog_ceil) => Ok(Some(prog_ceil)),
+        }
+    }
+
+    fn add_path(&self, app: &mut App, path: &Path) -> Result<AddOutcome, PExecutionError> {
+        let etype = etype_of_path(path, app)?;
+        let metadata = metadata_of_path(path).unwrap_or_default();
+        let bibliography = bibliography_of_metadata(&metadata);
+        let name = metadata.title.unwrap_or_else(|| name_from_path(path));
+        let tags = parent_section_tags(app.library(), path)?;
+
+        if !self.confirm(path, etype, &name, &tags)? {
+            return Ok(AddOutcome::Skipped {
+                reason: "declined".to_string(),
+            });
+        }
+
+        let progress = if libentity_has_progress(etype) {
+            match self.read_progceil(metadata.page_count)? {
+                Some(prog_ceil) => Some(Progress::new(prog_ceil)),
+                None => {
+                    return Ok(AddOutcome::Skipped {
+                        reason: "couldn't define progress ceiling".to_string(),
+                    })
+                }
+            }
+        } else {
+            None
+        };
+
+        let libentity_data = LibEntityData {
+            path: path.to_path_buf(),
+            description: None,
+            bibliography,
+            etype,
+            name,
+            progress,
+            tags,
+        };
+
+        app.library_mut().add_libentity(libentity_data)?;
+        libentity_added(app, path.to_path_buf());
+
+        Ok(AddOutcome::Added)
+    }
+
+    fn print_summary(&self, outcomes: Vec<(PathBuf, AddOutcome)>) {
+        let (added, skipped): (Vec<_>, Vec<_>) = outcomes
+            .into_iter()
+            .partition(|(_, outcome)| matches!(outcome, AddOutcome::Added));
+
+        println!("Added library entities ({}):", added.len());
+        added
+            .into_iter()
+            .for_each(|(path, _)| println!("    {}", path.to_string_lossy()));
+
+        if !skipped.is_empty() {
+            println!("Skipped files ({}):", skipped.len());
+            skipped.into_iter().for_each(|(path, outcome)| {
+                if let AddOutcome::Skipped { reason } = outcome {
+                    println!("    {}: {}", path.to_string_lossy(), reason);
+                }
+            });
+        }
+    }
+}
+
+impl PCommand for AddPCMD {
+    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
+        // Parent directories go before their content, so sections are added before the
+        // entities whose tags are taken from them.
+        let untracked_paths =
+            self.walker
+                .untracked_paths(app.library(), &self.path, self.recursive)?;
+
+        let mut outcomes = Vec::with_capacity(untracked_paths.len());
+        for path in untracked_paths {
+            let outcome = self.add_path(app, &path)?;
+            outcomes.push((path, outcome));
+        }
+
+        self.print_summary(outcomes);
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use crate::app::App;
 use crate::comps_appearance::parse_string_to_tags;
+use crate::entity_selector::EntitySelector;
 use crate::error_ext::ComError;
-use crate::id::ID;
 
 use super::{PCommand, PExecutionError};
 
@@ -9,23 +9,27 @@ use itertools::Itertools;
 
 #[derive(Debug, Clone)]
 pub struct AddTagsPCMD {
-    id: ID,
+    entity: EntitySelector,
     stried_tags: String,
 }
 
 impl AddTagsPCMD {
-    pub fn new(id: ID, stried_tags: String) -> Self {
-        AddTagsPCMD { id, stried_tags }
+    pub fn new(entity: EntitySelector, stried_tags: String) -> Self {
+        AddTagsPCMD {
+            entity,
+            stried_tags,
+        }
     }
 }
 
 impl PCommand for AddTagsPCMD {
     fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
-        let mut entitybase = match unsafe { app.library().storage() }.get_entitybase(self.id)? {
+        let id = self.entity.resolve_id(app.library())?;
+        let mut entitybase = match unsafe { app.library().storage() }.get_entitybase(id)? {
             Some(entitybase) => entitybase,
             None => {
                 return Err(
-                    ComError::from(format!("couldn't find entitybase for ID {}", self.id)).into(),
+                    ComError::from(format!("couldn't find entitybase for ID {}", id)).into(),
                 )
             }
         };
@@ -40,7 +44,7 @@ impl PCommand for AddTagsPCMD {
             .map(|s| s.clone())
             .collect();
 
-        unsafe { app.library_mut().storage_mut() }.update_entitybase(self.id, entitybase)?;
+        unsafe { app.library_mut().storage_mut() }.update_entitybase(id, entitybase)?;
 
         println!("The tags were added");
 
@@ -0,0 +1,45 @@
+use crate::app::App;
+use crate::entity_selector::EntitySelector;
+
+use super::{PCommand, PExecutionError};
+
+/// Adds the library entities to the collection in the given order. They're appended to the end
+/// unless the position (counted from one) is given. Library entities that are already in the
+/// collection keep their places.
+#[derive(Debug, Clone)]
+pub struct AddToCollectionPCMD {
+    name: String,
+    entities: Vec<EntitySelector>,
+    position: Option<usize>,
+}
+
+impl AddToCollectionPCMD {
+    pub fn new(name: String, entities: Vec<EntitySelector>, position: Option<usize>) -> Self {
+        AddToCollectionPCMD {
+            name,
+            entities,
+            position,
+        }
+    }
+}
+
+impl PCommand for AddToCollectionPCMD {
+    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
+        let mut ids = Vec::with_capacity(self.entities.len());
+        for entity in &self.entities {
+            ids.push(entity.resolve_id(app.library())?);
+        }
+
+        let position = self.position.map(|position| position.saturating_sub(1));
+        let added_count = app
+            .library_mut()
+            .add_to_collection(&self.name, &ids, position)?;
+
+        println!(
+            "{} library entities were added to collection '{}'",
+            added_count, self.name
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use crate::app::App;
+use crate::bibliography::BIBLIOGRAPHY_FIELDS;
+use crate::entity_selector::EntitySelector;
+use crate::error_ext::ComError;
+
+use super::{PCommand, PExecutionError};
+
+const ASSIGNMENT_SEPARATOR: char = '=';
+
+/// Prints bibliography of the library entity or, if assignments (`field=value`) are given, changes
+/// it. Empty value unsets the field.
+#[derive(Debug, Clone)]
+pub struct BibPCMD {
+    entity: EntitySelector,
+    assignments: Vec<String>,
+}
+
+impl BibPCMD {
+    pub fn new(entity: EntitySelector, assignments: Vec<String>) -> Self {
+        BibPCMD {
+            entity,
+            assignments,
+        }
+    }
+
+    fn print_bibliography(&self, app: &App) -> Result<(), PExecutionError> {
+        let id = self.entity.resolve_id(app.library())?;
+        let bibliography = match app.library().get_bibliography(id)? {
+            Some(bibliography) => bibliography,
+            None => {
+                println!("There's no bibliography");
+                return Ok(());
+            }
+        };
+
+        for field in BIBLIOGRAPHY_FIELDS {
+            if let Some(value) = bibliography.field(field)? {
+                println!("{}: {}", field, value);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl PCommand for BibPCMD {
+    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
+        if self.assignments.is_empty() {
+            return self.print_bibliography(app);
+        }
+
+        let id = self.entity.resolve_id(app.library())?;
+        let mut bibliography = app.library().get_bibliography(id)?.unwrap_or_default();
+
+        for assignment in &self.assignments {
+            let (field, value) = match assignment.split_once(ASSIGNMENT_SEPARATOR) {
+                Some(field_value) => field_value,
+                None => {
+                    return Err(ComError::from(format!(
+                        "use syntax 'field{}value' instead of '{}'",
+                        ASSIGNMENT_SEPARATOR, assignment
+                    ))
+                    .into())
+                }
+            };
+
+            bibliography.set_field(field.trim(), value)?;
+        }
+
+        app.library_mut()
+            .set_bibliography(id, bibliography.normalized()?)?;
+
+        println!("The bibliography was updated");
+
+        Ok(())
+    }
+}
@@ -1,21 +1,22 @@
 use crate::app::App;
 use crate::comps_appearance::progress_to_string;
+use crate::entity_selector::EntitySelector;
 use crate::error_ext::ComError;
-use crate::id::ID;
 use crate::progress_update::ProgressUpdate;
 
+use super::hooks::progress_changed;
 use super::{PCommand, PExecutionError};
 
 #[derive(Debug, Clone)]
 pub struct ChangeProgressPCMD {
-    id: ID,
+    entity: EntitySelector,
     progress_update: ProgressUpdate,
 }
 
 impl ChangeProgressPCMD {
-    pub fn new(id: ID, progress_update: ProgressUpdate) -> Self {
+    pub fn new(entity: EntitySelector, progress_update: ProgressUpdate) -> Self {
         ChangeProgressPCMD {
-            id,
+            entity,
             progress_update,
         }
     }
@@ -23,18 +24,19 @@ impl ChangeProgressPCMD {
 
 impl PCommand for ChangeProgressPCMD {
     fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
-        let mut progress = match unsafe { app.library().storage() }.get_progress(self.id)? {
+        let id = self.entity.resolve_id(app.library())?;
+        let mut progress = match unsafe { app.library().storage() }.get_progress(id)? {
             Some(progress) => progress,
             None => {
-                return Err(
-                    ComError::from(format!("couldn't find progress for ID {}", self.id)).into(),
-                )
+                return Err(ComError::from(format!("couldn't find progress for ID {}", id)).into())
             }
         };
 
+        let old_progress = progress;
         self.progress_update.execute_for(&mut progress)?;
 
-        unsafe { app.library_mut().storage_mut() }.update_progress(self.id, progress)?;
+        unsafe { app.library_mut().storage_mut() }.update_progress(id, progress)?;
+        progress_changed(app, id, old_progress, progress);
 
         println!(
             "The progress was updated to {}",
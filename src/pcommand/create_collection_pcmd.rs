@@ -0,0 +1,26 @@
+use crate::app::App;
+
+use super::{PCommand, PExecutionError};
+
+/// Creates an empty collection. Collections are ordered lists of library entities that don't
+/// depend on directories.
+#[derive(Debug, Clone)]
+pub struct CreateCollectionPCMD {
+    name: String,
+}
+
+impl CreateCollectionPCMD {
+    pub fn new(name: String) -> Self {
+        CreateCollectionPCMD { name }
+    }
+}
+
+impl PCommand for CreateCollectionPCMD {
+    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
+        app.library_mut().create_collection(&self.name)?;
+
+        println!("Collection '{}' was created", self.name);
+
+        Ok(())
+    }
+}
@@ -1,27 +1,31 @@
 use crate::app::App;
+use crate::entity_selector::EntitySelector;
 
+use super::hooks::hook_scripts;
 use super::{PCommand, PExecutionError};
 
-use std::path::PathBuf;
-
 #[derive(Debug, Clone)]
 pub struct DelLibentityPCMD {
-    path: PathBuf,
+    entity: EntitySelector,
 }
 
 impl DelLibentityPCMD {
-    pub fn new(path: PathBuf) -> Self {
-        DelLibentityPCMD { path }
+    pub fn new(entity: EntitySelector) -> Self {
+        DelLibentityPCMD { entity }
     }
 }
 
 impl PCommand for DelLibentityPCMD {
     fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
-        app.library_mut().del_libentity(self.path.clone())?;
+        let path = self.entity.resolve_path(app.library())?;
+        let libentity = app.library_mut().del_libentity(path.clone())?;
+        if let Some(scripts) = hook_scripts(app) {
+            scripts.on_delete(libentity);
+        }
 
         println!(
             "Libentity with path '{}' was deleted",
-            self.path.to_string_lossy()
+            path.to_string_lossy()
         );
 
         Ok(())
@@ -1,14 +1,17 @@
 use crate::app::App;
+use crate::comps_interaction::tag_matches;
 use crate::entity_base::Tag;
+use crate::entity_selector::EntitySelector;
 use crate::error_ext::ComError;
 use crate::error_ext::CommonizeResultExt;
-use crate::id::ID;
 
 use super::{PCommand, PExecutionError};
 
 use std::io::stdin;
 use std::num::ParseIntError;
 
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
 fn delete_indexes_in_vector<T>(base_vector: Vec<T>, delete_indexes: &[usize]) -> Vec<T> {
     base_vector
         .into_iter()
@@ -23,14 +26,42 @@ fn delete_indexes_in_vector<T>(base_vector: Vec<T>, delete_indexes: &[usize]) ->
         .collect()
 }
 
+/// Tags are deleted by names or glob patterns (`draft-*`). If there're no patterns, tags of the
+/// library entity are picked interactively by indexes.
+///
+/// Tags are deleted either from the single selected library entity or from all library entities
+/// matching each of the filter tags.
 #[derive(Debug, Clone)]
 pub struct DelTagsPCMD {
-    id: ID,
+    target: Option<EntitySelector>,
+    patterns: Vec<String>,
+    filters: Vec<Tag>,
 }
 
 impl DelTagsPCMD {
-    pub fn new(id: ID) -> Self {
-        DelTagsPCMD { id }
+    /// Without filters the first argument selects the library entity, the others are patterns.
+    /// With filters all arguments are patterns.
+    pub fn new(mut args: Vec<String>, filters: Vec<Tag>) -> Self {
+        let target = if filters.is_empty() && !args.is_empty() {
+            Some(EntitySelector::from(args.remove(0)))
+        } else {
+            None
+        };
+
+        DelTagsPCMD {
+            target,
+            patterns: args,
+            filters,
+        }
+    }
+
+    fn build_patterns(&self) -> Result<GlobSet, PExecutionError> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &self.patterns {
+            builder.add(Glob::new(pattern).commonize()?);
+        }
+
+        Ok(builder.build().commonize()?)
     }
 
     fn print_tags(&self, tags: &Vec<Tag>) {
@@ -54,27 +85,84 @@ impl DelTagsPCMD {
     }
 }
 
-impl PCommand for DelTagsPCMD {
-    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
-        let mut entitybase = match unsafe { app.library().storage() }.get_entitybase(self.id)? {
+impl DelTagsPCMD {
+    fn delete_from_libentity(
+        &self,
+        app: &mut App,
+        target: &EntitySelector,
+    ) -> Result<(), PExecutionError> {
+        let id = target.resolve_id(app.library())?;
+        let mut entitybase = match unsafe { app.library().storage() }.get_entitybase(id)? {
             Some(entitybase) => entitybase,
             None => {
                 return Err(
-                    ComError::from(format!("couldn't find entitybase for ID {}", self.id)).into(),
+                    ComError::from(format!("couldn't find entitybase for ID {}", id)).into(),
                 )
             }
         };
 
-        self.print_tags(entitybase.tags());
-
-        let indexes_to_delete = self.get_indexes_to_delete()?;
-
         let old_tags = std::mem::replace(entitybase.tags_mut(), Vec::new());
-        let done_tags = delete_indexes_in_vector(old_tags, &indexes_to_delete);
+        let old_count = old_tags.len();
+        let done_tags = if self.patterns.is_empty() {
+            self.print_tags(&old_tags);
+
+            let indexes_to_delete = self.get_indexes_to_delete()?;
+            delete_indexes_in_vector(old_tags, &indexes_to_delete)
+        } else {
+            let patterns = self.build_patterns()?;
+            old_tags
+                .into_iter()
+                .filter(|tag| !patterns.is_match(tag))
+                .collect()
+        };
+        let deleted_count = old_count - done_tags.len();
         _ = std::mem::replace(entitybase.tags_mut(), done_tags);
 
-        unsafe { app.library_mut().storage_mut() }.update_entitybase(self.id, entitybase)?;
+        unsafe { app.library_mut().storage_mut() }.update_entitybase(id, entitybase)?;
+
+        println!("{} tags were deleted", deleted_count);
 
         Ok(())
     }
+
+    fn delete_from_filtered(&self, app: &mut App) -> Result<(), PExecutionError> {
+        if self.patterns.is_empty() {
+            return Err(ComError::from(
+                "tags to delete must be given when the library entities are filtered".to_string(),
+            )
+            .into());
+        }
+
+        let patterns = self.build_patterns()?;
+        let changed_count = app.library_mut().rewrite_tags(|tags| {
+            let is_filtered = self
+                .filters
+                .iter()
+                .all(|filter| tags.iter().any(|tag| tag_matches(tag, filter)));
+            if !is_filtered {
+                return tags.to_vec();
+            }
+
+            tags.iter()
+                .filter(|tag| !patterns.is_match(tag))
+                .cloned()
+                .collect()
+        })?;
+
+        println!("Tags were deleted from {} library entities", changed_count);
+
+        Ok(())
+    }
+}
+
+impl PCommand for DelTagsPCMD {
+    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
+        match &self.target {
+            Some(target) => self.delete_from_libentity(app, target),
+            None if !self.filters.is_empty() => self.delete_from_filtered(app),
+            None => Err(
+                ComError::from("library entity or filter tags must be given".to_string()).into(),
+            ),
+        }
+    }
 }
@@ -0,0 +1,29 @@
+use crate::app::App;
+
+use super::{PCommand, PExecutionError};
+
+/// Deletes the collection. Its members stay in the library.
+#[derive(Debug, Clone)]
+pub struct DeleteCollectionPCMD {
+    name: String,
+}
+
+impl DeleteCollectionPCMD {
+    pub fn new(name: String) -> Self {
+        DeleteCollectionPCMD { name }
+    }
+}
+
+impl PCommand for DeleteCollectionPCMD {
+    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
+        let members = app.library_mut().delete_collection(&self.name)?;
+
+        println!(
+            "Collection '{}' with {} library entities was deleted",
+            self.name,
+            members.len()
+        );
+
+        Ok(())
+    }
+}
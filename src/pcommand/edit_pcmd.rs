@@ -0,0 +1,199 @@
+use crate::app::App;
+use crate::bibliography::{Bibliography, BIBLIOGRAPHY_FIELDS};
+use crate::comps_interaction::tag_is_valid;
+use crate::editor::edit_text;
+use crate::entity_base::Tag;
+use crate::entity_selector::EntitySelector;
+use crate::error_ext::{ComError, ComResult, CommonizeResultExt};
+use crate::libentity::{LibEntity, LibEntityData};
+use crate::progress::Progress;
+
+use super::{PCommand, PExecutionError};
+
+use std::io::{stdin, stdout, Error as IoError, Write};
+
+use itertools::Itertools;
+use serde_derive::{Deserialize, Serialize};
+
+const EDIT_FILE_EXTENSION: &str = "toml";
+
+fn read_input_stdin() -> Result<String, IoError> {
+    let mut string = String::new();
+    stdin().read_line(&mut string)?;
+
+    Ok(string.trim().to_string())
+}
+
+fn write_stdout(string: &str) -> Result<(), IoError> {
+    stdout().write_all(string.as_bytes())?;
+    stdout().flush()?;
+
+    Ok(())
+}
+
+/// Attributes of library entity that can be changed by user. It's rendered to TOML.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct EditableFields {
+    name: String,
+    tags: Vec<Tag>,
+    /// Empty description means that there's no description.
+    #[serde(default)]
+    description: String,
+    /// Exists only for documents.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    progress_ceiling: Option<usize>,
+    /// Missing fields of the bibliography are unset.
+    #[serde(default)]
+    bibliography: Bibliography,
+}
+
+impl EditableFields {
+    fn from_libentity(libentity: &LibEntity) -> Self {
+        EditableFields {
+            name: libentity.name().clone(),
+            tags: libentity.tags().clone(),
+            description: libentity.description().cloned().unwrap_or_default(),
+            progress_ceiling: libentity.progress().map(|progress| progress.ceiling()),
+            bibliography: libentity.bibliography().cloned().unwrap_or_default(),
+        }
+    }
+
+    /// Checks the fields against the invariants (look at the `pcommand` module) and makes new
+    /// data for the library entity.
+    fn into_libentity_data(self, libentity: &LibEntity) -> ComResult<LibEntityData> {
+        let name = self.name.trim().to_string();
+        if name.is_empty() {
+            return Err("name mustn't be empty".into());
+        }
+
+        for tag in &self.tags {
+            if !tag_is_valid(tag) {
+                return Err(format!("tag '{}' mustn't be empty or contain spaces", tag).into());
+            }
+        }
+        let tags = self.tags.into_iter().unique().collect();
+
+        let progress = match (libentity.progress(), self.progress_ceiling) {
+            (Some(_), Some(0)) => return Err("progress ceiling must be at least 1".into()),
+            (Some(progress), Some(ceiling)) if progress.passed() > ceiling => {
+                return Err(format!(
+                    "progress ceiling {} is less than passed value {}",
+                    ceiling,
+                    progress.passed()
+                )
+                .into())
+            }
+            (Some(progress), Some(ceiling)) => {
+                Some(Progress::with_passed(progress.passed(), ceiling))
+            }
+            (Some(_), None) => return Err("documents must have progress ceiling".into()),
+            (None, Some(_)) => return Err("only documents can have progress ceiling".into()),
+            (None, None) => None,
+        };
+
+        let description = match self.description.trim() {
+            "" => None,
+            description => Some(description.to_string()),
+        };
+
+        let bibliography = self.bibliography.normalized()?;
+
+        Ok(LibEntityData {
+            path: libentity.path().clone(),
+            name,
+            etype: libentity.etype(),
+            tags,
+            progress,
+            description,
+            bibliography,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EditPCMD {
+    entity: EntitySelector,
+}
+
+impl EditPCMD {
+    pub fn new(entity: EntitySelector) -> Self {
+        EditPCMD { entity }
+    }
+
+    fn render(&self, libentity: &LibEntity, fields: &EditableFields) -> ComResult<String> {
+        let header = format!(
+            "# Attributes of the library entity '{}' (ID {}).\n\
+             # Tags mustn't contain spaces. Empty description means no description.\n\
+             # Bibliography fields: {}.\n\n",
+            libentity.path().to_string_lossy(),
+            libentity.id(),
+            BIBLIOGRAPHY_FIELDS.join(", "),
+        );
+
+        Ok(header + &toml::to_string(fields).commonize()?)
+    }
+
+    fn ask_reopen(&self) -> Result<bool, IoError> {
+        write_stdout("Reopen the editor? [Y/n]: ")?;
+        let answer = read_input_stdin()?.to_lowercase();
+
+        Ok(answer.is_empty() || answer == "y" || answer == "yes")
+    }
+}
+
+impl PCommand for EditPCMD {
+    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
+        let path = self.entity.resolve_path(app.library())?;
+        let libentity = match app.library().get_libentity(path.clone())? {
+            Some(libentity) => libentity,
+            None => {
+                return Err(ComError::from(format!(
+                    "couldn't find library entity with path '{}'",
+                    path.to_string_lossy()
+                ))
+                .into());
+            }
+        };
+        let old_fields = EditableFields::from_libentity(&libentity);
+        let mut content = self.render(&libentity, &old_fields)?;
+
+        // The editor is reopened with the user's content until it's valid or user gives up.
+        let libentity_data = loop {
+            content = edit_text(&content, &libentity.id().to_string(), EDIT_FILE_EXTENSION)?;
+
+            let result = toml::from_str::<EditableFields>(&content)
+                .commonize()
+                .and_then(|fields| {
+                    if fields == old_fields {
+                        Ok(None)
+                    } else {
+                        fields.into_libentity_data(&libentity).map(Some)
+                    }
+                });
+
+            match result {
+                Ok(Some(libentity_data)) => break libentity_data,
+                Ok(None) => {
+                    println!("Nothing was changed");
+                    return Ok(());
+                }
+                Err(err) => {
+                    println!("Invalid attributes: {}", err);
+
+                    if !self.ask_reopen()? {
+                        return Err(err.into());
+                    }
+                }
+            }
+        };
+
+        app.library_mut().update_libentity(libentity_data)?;
+
+        println!(
+            "Libentity with path '{}' was updated",
+            path.to_string_lossy()
+        );
+
+        Ok(())
+    }
+}
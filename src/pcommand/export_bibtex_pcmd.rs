@@ -0,0 +1,124 @@
+use crate::app::App;
+use crate::bibtex::{citation_key, BibtexEntry, BIBTEX_AUTHORS_SEPARATOR};
+use crate::entity_base::EntityType;
+use crate::libentity::LibEntity;
+use crate::libentity_filter::LibEntityFilter;
+
+use super::{PCommand, PExecutionError};
+
+use std::collections::HashMap;
+
+/// Makes the entry without the citation key.
+fn bibtex_entry_of(libentity: &LibEntity) -> BibtexEntry {
+    let bibliography = libentity.bibliography().cloned().unwrap_or_default();
+    let entry_type = if bibliography.isbn.is_some() || bibliography.publisher.is_some() {
+        "book"
+    } else {
+        "misc"
+    };
+    let mut entry = BibtexEntry::new(entry_type.to_string(), String::new());
+
+    entry.push_field("title", libentity.name().clone());
+    entry.push_field(
+        "author",
+        bibliography.authors.join(BIBTEX_AUTHORS_SEPARATOR),
+    );
+    entry.push_field(
+        "year",
+        bibliography
+            .year
+            .map(|year| year.to_string())
+            .unwrap_or_default(),
+    );
+    entry.push_field("publisher", bibliography.publisher.unwrap_or_default());
+    entry.push_field("edition", bibliography.edition.unwrap_or_default());
+    entry.push_field("series", bibliography.series.unwrap_or_default());
+    entry.push_field(
+        "number",
+        bibliography
+            .series_index
+            .map(|index| index.to_string())
+            .unwrap_or_default(),
+    );
+    entry.push_field("isbn", bibliography.isbn.unwrap_or_default());
+    entry.push_field("doi", bibliography.doi.unwrap_or_default());
+    entry.push_field("language", bibliography.language.unwrap_or_default());
+    entry.push_field("keywords", libentity.tags().join(", "));
+    entry.push_field("file", libentity.path().to_string_lossy().to_string());
+
+    entry
+}
+
+/// Returns suffix for the citation key that is used `used_count` times already: ``, `b`, `c`,
+/// ..., `z`, `aa`, `ab`, etc. The first key has no suffix and counts as `a`.
+fn key_suffix(used_count: usize) -> String {
+    if used_count == 0 {
+        return String::new();
+    }
+
+    // Letters are digits of the bijective base-26 numeral of `used_count + 1`: `a` is 1.
+    let mut number = used_count + 1;
+    let mut letters = Vec::new();
+    while number > 0 {
+        number -= 1;
+        letters.push(char::from(b'a' + (number % 26) as u8));
+        number /= 26;
+    }
+
+    letters.iter().rev().collect()
+}
+
+/// Prints BibTeX entries of the filtered library entities. Sections aren't exported.
+///
+/// Citation keys are made of the bibliography and the name. Library entities with the same keys
+/// get suffixes in order of their IDs among all library entities, so the filter doesn't change
+/// keys. Adding or deleting a library entity with the same key may change them though, cause IDs
+/// of deleted library entities are reused.
+#[derive(Debug, Clone)]
+pub struct ExportBibtexPCMD {
+    filter: LibEntityFilter,
+}
+
+impl ExportBibtexPCMD {
+    pub fn new(filter: LibEntityFilter) -> Self {
+        ExportBibtexPCMD { filter }
+    }
+}
+
+impl PCommand for ExportBibtexPCMD {
+    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
+        let mut libentities = app
+            .library()
+            .get_libentities()?
+            .into_iter()
+            .filter(|libentity| libentity.etype() != EntityType::Section)
+            .collect::<Vec<_>>();
+        libentities.sort_by_key(|libentity| libentity.id());
+
+        // Suffixes are counted over all library entities, filtered ones are just skipped.
+        let mut used_keys: HashMap<String, usize> = HashMap::new();
+        let mut entries = Vec::new();
+        for libentity in &libentities {
+            let bibliography = libentity.bibliography().cloned().unwrap_or_default();
+            let key = citation_key(&bibliography.authors, bibliography.year, libentity.name());
+
+            let used_count = used_keys.entry(key.clone()).or_insert(0);
+            let suffix = key_suffix(*used_count);
+            *used_count += 1;
+
+            if !self.filter.matches(libentity) {
+                continue;
+            }
+            let mut entry = bibtex_entry_of(libentity);
+            entry.key = format!("{}{}", key, suffix);
+
+            entries.push(entry.to_string());
+        }
+
+        if !entries.is_empty() {
+            println!("{}", entries.join("\n\n"));
+        }
+
+        Ok(())
+    }
+}
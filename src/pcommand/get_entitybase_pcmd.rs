@@ -1,30 +1,28 @@
 use crate::app::App;
 use crate::comps_appearance::entitybase_to_fullinfo_string;
-use crate::id::ID;
+use crate::entity_selector::EntitySelector;
 
 use super::{PCommand, PExecutionError};
 
 #[derive(Debug, Clone)]
 pub struct GetEntitybasePCMD {
-    id: ID,
+    entity: EntitySelector,
 }
 
 impl GetEntitybasePCMD {
-    pub fn new(id: ID) -> Self {
-        GetEntitybasePCMD { id }
+    pub fn new(entity: EntitySelector) -> Self {
+        GetEntitybasePCMD { entity }
     }
 }
 
 impl PCommand for GetEntitybasePCMD {
     fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
-        let maybe_entitybase = unsafe { app.library().storage() }.get_entitybase(self.id)?;
+        let id = self.entity.resolve_id(app.library())?;
+        let maybe_entitybase = unsafe { app.library().storage() }.get_entitybase(id)?;
 
         match maybe_entitybase {
             Some(entitybase) => println!("{}", entitybase_to_fullinfo_string(&entitybase)),
-            None => println!(
-                "Couldn't find entity base associated with the {} ID",
-                self.id
-            ),
+            None => println!("Couldn't find entity base associated with the {} ID", id),
         }
 
         Ok(())
@@ -1,27 +1,27 @@
 use crate::app::App;
+use crate::entity_selector::{EntitySelector, EntitySelectorError};
 
 use super::{PCommand, PExecutionError};
 
-use std::path::PathBuf;
-
 #[derive(Debug, Clone)]
 pub struct GetIDPCMD {
-    path: PathBuf,
+    entity: EntitySelector,
 }
 
 impl GetIDPCMD {
-    pub fn new(path: PathBuf) -> Self {
-        GetIDPCMD { path }
+    pub fn new(entity: EntitySelector) -> Self {
+        GetIDPCMD { entity }
     }
 }
 
 impl PCommand for GetIDPCMD {
     fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
-        let maybe_id = unsafe { app.library().storage() }.get_id(self.path.clone())?;
-
-        match maybe_id {
-            Some(id) => println!("ID: {}", id),
-            None => println!("There's no ID linked to given path"),
+        match self.entity.resolve_id(app.library()) {
+            Ok(id) => println!("ID: {}", id),
+            Err(EntitySelectorError::NotFound(_)) => {
+                println!("There's no ID linked to given path")
+            }
+            Err(err) => return Err(err.into()),
         }
 
         Ok(())
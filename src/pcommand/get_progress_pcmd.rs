@@ -1,33 +1,32 @@
 use crate::app::App;
 use crate::comps_appearance::progress_to_string;
+use crate::entity_selector::EntitySelector;
 use crate::error_ext::ComError;
-use crate::id::ID;
 
 use super::{PCommand, PExecutionError};
 
 #[derive(Debug, Clone)]
 pub struct GetProgressPCMD {
-    id: ID,
+    entity: EntitySelector,
 }
 
 impl GetProgressPCMD {
-    pub fn new(id: ID) -> Self {
-        GetProgressPCMD { id }
+    pub fn new(entity: EntitySelector) -> Self {
+        GetProgressPCMD { entity }
     }
 }
 
 impl PCommand for GetProgressPCMD {
     fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
-        let maybe_progress = unsafe { app.library().storage() }.get_progress(self.id)?;
+        let id = self.entity.resolve_id(app.library())?;
+        let maybe_progress = unsafe { app.library().storage() }.get_progress(id)?;
 
         match maybe_progress {
             Some(progress) => println!("Progress: {}", progress_to_string(&progress)),
             None => {
-                return Err(ComError::from(format!(
-                    "couldn't find the progress for ID {}",
-                    self.id
-                ))
-                .into())
+                return Err(
+                    ComError::from(format!("couldn't find the progress for ID {}", id)).into(),
+                )
             }
         }
 
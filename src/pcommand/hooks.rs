@@ -0,0 +1,64 @@
+//! Here are helpers calling hooks of the scripts file after commands change the library. Changes
+//! are already saved when hooks are called, so failures of hooks are only reported.
+
+use crate::app::App;
+use crate::id::ID;
+use crate::progress::Progress;
+use crate::scripts::Scripts;
+
+use std::path::PathBuf;
+
+/// Returns scripts for calling hooks. Hooks are called after the change is saved, so if scripts
+/// can't be loaded, hooks are skipped with a warning instead of failing the command.
+pub(super) fn hook_scripts(app: &App) -> Option<&Scripts> {
+    match app.scripts() {
+        Ok(scripts) => Some(scripts),
+        Err(err) => {
+            eprintln!("Hooks were skipped: {}", err);
+            None
+        }
+    }
+}
+
+/// Calls the `on_add` hook for the just added library entity.
+pub(super) fn libentity_added(app: &App, path: PathBuf) {
+    let scripts = match hook_scripts(app) {
+        Some(scripts) => scripts,
+        None => return,
+    };
+
+    match app.library().get_libentity(path) {
+        Ok(Some(libentity)) => scripts.on_add(libentity),
+        Ok(None) => eprintln!("Hook 'on_add' was skipped: couldn't find the added library entity"),
+        Err(err) => eprintln!("Hook 'on_add' was skipped: {}", err),
+    }
+}
+
+/// Calls the `on_progress` hook and, if the document has just reached its ceiling, the `on_finish`
+/// one.
+pub(super) fn progress_changed(app: &App, id: ID, old_progress: Progress, new_progress: Progress) {
+    let scripts = match hook_scripts(app) {
+        Some(scripts) => scripts,
+        None => return,
+    };
+
+    let libentity = match app.library().get_libentity_by_id(id) {
+        Ok(Some(libentity)) => libentity,
+        Ok(None) => {
+            eprintln!(
+                "Hooks were skipped: couldn't find library entity with ID {}",
+                id
+            );
+            return;
+        }
+        Err(err) => {
+            eprintln!("Hooks were skipped: {}", err);
+            return;
+        }
+    };
+
+    scripts.on_progress(libentity.clone(), old_progress, new_progress);
+    if !old_progress.is_finished() && new_progress.is_finished() {
+        scripts.on_finish(libentity);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::app::App;
+use crate::bibliography::Bibliography;
+use crate::bibtex::{parse_bibtex, BibtexEntry, BIBTEX_AUTHORS_SEPARATOR};
+use crate::error_ext::{ComResult, CommonizeResultExt};
+use crate::id::ID;
+use crate::libentity::LibEntity;
+
+use super::{PCommand, PExecutionError};
+
+use std::path::{Path, PathBuf};
+
+/// BibTeX fields and the bibliography fields they're imported to. Authors are imported
+/// separately.
+const IMPORTED_FIELDS: [(&str, &str); 8] = [
+    ("year", "year"),
+    ("publisher", "publisher"),
+    ("isbn", "isbn"),
+    ("doi", "doi"),
+    ("language", "language"),
+    ("edition", "edition"),
+    ("series", "series"),
+    ("number", "series_index"),
+];
+
+/// Returns paths the `file` field may point to. The field may contain several files separated by
+/// `;` in the `description:path:type` form of JabRef. Paths are tried relative to the library and
+/// to the BibTeX file.
+fn candidate_paths(file_field: &str, bibtex_dir: &Path) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+
+    for file in file_field.split(';') {
+        let mut parts = vec![file.trim()];
+        if file.contains(':') {
+            parts.extend(file.split(':').map(|part| part.trim()));
+        }
+
+        for part in parts.into_iter().filter(|part| !part.is_empty()) {
+            paths.push(PathBuf::from(part));
+            paths.push(bibtex_dir.join(part));
+        }
+    }
+
+    paths
+}
+
+/// Fills the bibliography by fields of the entry. Fields missing in the entry are left as is.
+fn import_fields(bibliography: &mut Bibliography, entry: &BibtexEntry) -> ComResult<()> {
+    if let Some(authors) = entry.field("author") {
+        bibliography.authors = authors
+            .split(BIBTEX_AUTHORS_SEPARATOR)
+            .map(|author| author.trim().to_string())
+            .filter(|author| !author.is_empty())
+            .collect();
+    }
+
+    for (bibtex_field, bibliography_field) in IMPORTED_FIELDS {
+        if let Some(value) = entry.field(bibtex_field) {
+            bibliography.set_field(bibliography_field, value)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Fills bibliographies of the library entities by entries of the BibTeX file. Entries are matched
+/// with library entities by the `file` field or, if there's no such field or the file isn't
+/// tracked, by the title equal to the name.
+#[derive(Debug, Clone)]
+pub struct ImportBibtexPCMD {
+    bibtex_path: PathBuf,
+}
+
+impl ImportBibtexPCMD {
+    pub fn new(bibtex_path: PathBuf) -> Self {
+        ImportBibtexPCMD { bibtex_path }
+    }
+
+    fn match_entry(
+        &self,
+        app: &App,
+        libentities: &[LibEntity],
+        entry: &BibtexEntry,
+    ) -> Result<Result<ID, String>, PExecutionError> {
+        if let Some(file_field) = entry.field("file") {
+            let bibtex_dir = self.bibtex_path.parent().unwrap_or(Path::new(""));
+
+            for path in candidate_paths(file_field, bibtex_dir) {
+                // Paths that go beyond the library can't be tracked.
+                if let Ok(Some(id)) = app.library().get_id(path) {
+                    return Ok(Ok(id));
+                }
+            }
+        }
+
+        // Entries whose files aren't tracked are matched by the title.
+        let title = match (entry.field("title"), entry.field("file")) {
+            (Some(title), _) => title.to_lowercase(),
+            (None, Some(file_field)) => {
+                return Ok(Err(format!("file '{}' isn't tracked", file_field)))
+            }
+            (None, None) => return Ok(Err("there're neither file nor title".to_string())),
+        };
+        let matched = libentities
+            .iter()
+            .filter(|libentity| libentity.name().to_lowercase() == title)
+            .collect::<Vec<_>>();
+
+        Ok(match matched.as_slice() {
+            [libentity] => Ok(libentity.id()),
+            [] => Err("no library entity has the title".to_string()),
+            _ => Err("several library entities have the title".to_string()),
+        })
+    }
+}
+
+impl PCommand for ImportBibtexPCMD {
+    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
+        let content = std::fs::read_to_string(&self.bibtex_path)?;
+        let entries = parse_bibtex(&content).commonize()?;
+        let libentities = app.library().get_libentities()?;
+
+        let mut updated = Vec::new();
+        let mut unmatched = Vec::new();
+        for entry in &entries {
+            let id = match self.match_entry(app, &libentities, entry)? {
+                Ok(id) => id,
+                Err(reason) => {
+                    unmatched.push((entry.key.clone(), reason));
+                    continue;
+                }
+            };
+
+            let mut bibliography = app.library().get_bibliography(id)?.unwrap_or_default();
+            let bibliography = match import_fields(&mut bibliography, entry)
+                .and_then(|_| bibliography.normalized())
+            {
+                Ok(bibliography) => bibliography,
+                Err(err) => {
+                    unmatched.push((entry.key.clone(), err.to_string()));
+                    continue;
+                }
+            };
+
+            app.library_mut().set_bibliography(id, bibliography)?;
+            updated.push((entry.key.clone(), id));
+        }
+
+        println!("Updated library entities ({}):", updated.len());
+        for (key, id) in updated {
+            println!("    {} -> #{}", key, id);
+        }
+
+        if !unmatched.is_empty() {
+            println!("Unmatched entries ({}):", unmatched.len());
+            for (key, reason) in unmatched {
+                println!("    {}: {}", key, reason);
+            }
+        }
+
+        Ok(())
+    }
+}
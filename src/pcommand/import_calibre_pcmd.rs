@@ -0,0 +1,188 @@
+use crate::app::App;
+use crate::comps_interaction::{libentity_has_progress, tag_is_valid};
+use crate::entity_base::Tag;
+use crate::error_ext::CommonizeResultExt;
+use crate::libentity::LibEntityData;
+use crate::metadata::read_opf_metadata;
+use crate::progress::Progress;
+
+use super::add_libentity_pcmd::{bibliography_of_metadata, etype_of_path, metadata_of_path};
+use super::hooks::libentity_added;
+use super::{PCommand, PExecutionError};
+
+use std::ffi::OsStr;
+use std::io::Error as IoError;
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+/// Calibre keeps metadata of each book in this file in the directory of the book.
+const CALIBRE_METADATA_FILE: &str = "metadata.opf";
+/// Calibre keeps cover of the book in the directory of the book as `cover.jpg`.
+const CALIBRE_COVER_STEM: &str = "cover";
+
+/// Makes tag of the Calibre tag. Whitespaces are replaced by `-` because tags can't contain them.
+/// Returns `None` if the result still isn't a valid tag.
+fn tag_of_calibre_tag(calibre_tag: &str) -> Option<Tag> {
+    let tag = calibre_tag.split_whitespace().collect::<Vec<_>>().join("-");
+
+    if tag_is_valid(&tag) {
+        Some(tag)
+    } else {
+        None
+    }
+}
+
+/// Returns the book file in the directory of the Calibre book. If there're several formats of the
+/// book, the one whose extension goes first in the `document_extension` list of the config is
+/// taken.
+fn book_file(book_dir: &Path, document_extensions: &[String]) -> Result<Option<PathBuf>, IoError> {
+    let mut files = Vec::new();
+    for dir_entry in std::fs::read_dir(book_dir)? {
+        let path = dir_entry?.path();
+
+        let is_book = path.is_file()
+            && path.file_name() != Some(OsStr::new(CALIBRE_METADATA_FILE))
+            && path.file_stem() != Some(OsStr::new(CALIBRE_COVER_STEM));
+        if is_book {
+            files.push(path);
+        }
+    }
+    files.sort();
+
+    let extension_rank = |path: &PathBuf| {
+        let extension = path
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        document_extensions
+            .iter()
+            .position(|document_extension| *document_extension == extension)
+            .unwrap_or(document_extensions.len())
+    };
+
+    Ok(files.into_iter().min_by_key(extension_rank))
+}
+
+/// Adds books of the Calibre library to the library. Calibre library must be inside of the
+/// library. Books are taken from directories containing `metadata.opf`. Title, authors, tags and
+/// description are read from this file, page count is read from the book file itself.
+#[derive(Debug, Clone)]
+pub struct ImportCalibrePCMD {
+    calibre_dir: PathBuf,
+}
+
+impl ImportCalibrePCMD {
+    pub fn new(calibre_dir: PathBuf) -> Self {
+        ImportCalibrePCMD { calibre_dir }
+    }
+
+    /// Returns path of the added book or the reason why the book couldn't be added.
+    fn import_book(
+        &self,
+        app: &mut App,
+        book_dir: &Path,
+    ) -> Result<Result<PathBuf, String>, PExecutionError> {
+        let opf_content = match std::fs::read_to_string(book_dir.join(CALIBRE_METADATA_FILE)) {
+            Ok(opf_content) => opf_content,
+            Err(err) => return Ok(Err(format!("couldn't read metadata: {}", err))),
+        };
+        let metadata = match read_opf_metadata(&opf_content) {
+            Ok(metadata) => metadata,
+            Err(err) => return Ok(Err(format!("couldn't read metadata: {}", err))),
+        };
+
+        let path = match book_file(book_dir, app.config()?.document_extension())? {
+            Some(path) => path,
+            None => return Ok(Err("there's no book file".to_string())),
+        };
+        match app.library().get_id(path.clone()) {
+            Ok(None) => (),
+            Ok(Some(_)) => {
+                return Ok(Err(format!(
+                    "'{}' is already tracked",
+                    path.to_string_lossy()
+                )))
+            }
+            // Paths that go beyond the library can't be tracked.
+            Err(err) => return Ok(Err(format!("couldn't track the book file: {}", err))),
+        }
+
+        let name = match &metadata.title {
+            Some(title) => title.clone(),
+            None => return Ok(Err("there's no title".to_string())),
+        };
+        let etype = etype_of_path(&path, app)?;
+        let progress = if libentity_has_progress(etype) {
+            match metadata_of_path(&path).and_then(|metadata| metadata.page_count) {
+                Some(page_count) => Some(Progress::new(page_count)),
+                None => return Ok(Err("couldn't detect page count".to_string())),
+            }
+        } else {
+            None
+        };
+        let mut tags = Vec::new();
+        for tag in metadata
+            .tags
+            .iter()
+            .filter_map(|tag| tag_of_calibre_tag(tag))
+        {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+
+        let libentity_data = LibEntityData {
+            path: path.clone(),
+            description: metadata.description.clone(),
+            bibliography: bibliography_of_metadata(&metadata),
+            etype,
+            name,
+            progress,
+            tags,
+        };
+        app.library_mut().add_libentity(libentity_data)?;
+        libentity_added(app, path.clone());
+
+        Ok(Ok(path))
+    }
+}
+
+impl PCommand for ImportCalibrePCMD {
+    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
+        let mut book_dirs = Vec::new();
+        for dir_entry in WalkDir::new(&self.calibre_dir).sort_by_file_name() {
+            let dir_entry = dir_entry.commonize()?;
+
+            if dir_entry.file_name() == CALIBRE_METADATA_FILE {
+                if let Some(book_dir) = dir_entry.path().parent() {
+                    book_dirs.push(book_dir.to_path_buf());
+                }
+            }
+        }
+
+        let mut added = Vec::new();
+        let mut unmapped = Vec::new();
+        for book_dir in book_dirs {
+            match self.import_book(app, &book_dir)? {
+                Ok(path) => added.push(path),
+                Err(reason) => unmapped.push((book_dir, reason)),
+            }
+        }
+
+        println!("Added library entities ({}):", added.len());
+        for path in added {
+            println!("    {}", path.to_string_lossy());
+        }
+
+        if !unmapped.is_empty() {
+            println!("Unmapped books ({}):", unmapped.len());
+            for (book_dir, reason) in unmapped {
+                println!("    {}: {}", book_dir.to_string_lossy(), reason);
+            }
+        }
+
+        Ok(())
+    }
+}
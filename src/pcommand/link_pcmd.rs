@@ -0,0 +1,57 @@
+use crate::app::App;
+use crate::entity_selector::EntitySelector;
+use crate::error_ext::ComError;
+use crate::relation::{relation_kind_is_valid, Relation};
+
+use super::{PCommand, PExecutionError};
+
+/// Adds the relation going from the source library entity to the target one, e.g. `a.pdf
+/// translation_of b.pdf`.
+#[derive(Debug, Clone)]
+pub struct LinkPCMD {
+    source: EntitySelector,
+    relation: String,
+    target: EntitySelector,
+}
+
+impl LinkPCMD {
+    pub fn new(source: EntitySelector, relation: String, target: EntitySelector) -> Self {
+        LinkPCMD {
+            source,
+            relation,
+            target,
+        }
+    }
+}
+
+impl PCommand for LinkPCMD {
+    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
+        if !relation_kind_is_valid(&self.relation) {
+            return Err(ComError::from(format!(
+                "relation '{}' may contain only ASCII letters, digits, '_' and '-'",
+                self.relation
+            ))
+            .into());
+        }
+
+        let source_id = self.source.resolve_id(app.library())?;
+        let target_id = self.target.resolve_id(app.library())?;
+        if source_id == target_id {
+            return Err(
+                ComError::from("library entity can't be related with itself".to_string()).into(),
+            );
+        }
+
+        let relation = Relation {
+            kind: self.relation.clone(),
+            target: target_id,
+        };
+        if app.library_mut().add_relation(source_id, relation)? {
+            println!("#{} {} #{}", source_id, self.relation, target_id);
+        } else {
+            println!("The relation already exists");
+        }
+
+        Ok(())
+    }
+}
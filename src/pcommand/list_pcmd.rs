@@ -1,5 +1,6 @@
 use crate::app::App;
 use crate::error_ext::ComError;
+use crate::libentity_filter::LibEntityFilter;
 use crate::scripts::Context;
 
 use super::{PCommand, PExecutionError};
@@ -23,25 +24,44 @@ impl ListMode {
 #[derive(Debug, Clone)]
 pub struct ListPCMD {
     mode: ListMode,
+    filter: LibEntityFilter,
+    /// Names of filters defined in the scripts file.
+    script_filters: Vec<String>,
+    /// Name of sorter defined in the scripts file.
+    sorter: Option<String>,
 }
 
 impl ListPCMD {
-    pub fn new(mode: ListMode) -> Self {
-        ListPCMD { mode }
+    pub fn new(
+        mode: ListMode,
+        filter: LibEntityFilter,
+        script_filters: Vec<String>,
+        sorter: Option<String>,
+    ) -> Self {
+        ListPCMD {
+            mode,
+            filter,
+            script_filters,
+            sorter,
+        }
     }
 }
 
 impl PCommand for ListPCMD {
     fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
-        let paths = unsafe { app.library().storage() }.keys_path()?;
-
-        let mut libentities = Vec::with_capacity(paths.len());
-        for path in paths {
-            let libentity = match app.library().get_libentity(path)? {
-                Some(libentity) => libentity,
-                None => return Err(ComError::from(format!("invalid library entity")).into()),
-            };
-            libentities.push(libentity);
+        let mut libentities = app
+            .library()
+            .get_libentities()?
+            .into_iter()
+            .filter(|libentity| self.filter.matches(libentity))
+            .collect::<Vec<_>>();
+        for script_filter in &self.script_filters {
+            libentities = app
+                .scripts()?
+                .filter_libentities(script_filter, libentities)?;
+        }
+        if let Some(sorter) = &self.sorter {
+            libentities = app.scripts()?.sort_libentities(sorter, libentities)?;
         }
 
         let context = match Context::auto() {
@@ -54,8 +74,8 @@ impl PCommand for ListPCMD {
         };
 
         let result = match self.mode {
-            ListMode::Wide => app.scripts().list_output_wide(libentities, context)?,
-            ListMode::Narrow => app.scripts().list_output_narrow(libentities, context)?,
+            ListMode::Wide => app.scripts()?.list_output_wide(libentities, context)?,
+            ListMode::Narrow => app.scripts()?.list_output_narrow(libentities, context)?,
         };
         println!("{}", result.trim());
 
@@ -1,30 +1,30 @@
 use crate::app::App;
+use crate::entity_selector::EntitySelector;
 use crate::error_ext::ComError;
 use crate::scripts::Context;
 
 use super::{PCommand, PExecutionError};
 
-use std::path::PathBuf;
-
 #[derive(Debug, Clone)]
 pub struct LookPCMD {
-    path: PathBuf,
+    entity: EntitySelector,
 }
 
 impl LookPCMD {
-    pub fn new(path: PathBuf) -> Self {
-        LookPCMD { path }
+    pub fn new(entity: EntitySelector) -> Self {
+        LookPCMD { entity }
     }
 }
 
 impl PCommand for LookPCMD {
     fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
-        let libentity = match app.library().get_libentity(self.path.clone())? {
-            Some(libentity) => libentity,
+        let path = self.entity.resolve_path(app.library())?;
+        let libentity = match app.library().get_libentity(path.clone())? {
+            Some(libentity) => app.library().with_backlinks(libentity)?,
             None => {
                 return Err(ComError::from(format!(
                     "couldn't find library entity with path '{}'",
-                    self.path.to_string_lossy()
+                    path.to_string_lossy()
                 ))
                 .into());
             }
@@ -38,7 +38,7 @@ impl PCommand for LookPCMD {
             }
         };
 
-        let result = app.scripts().look_output(libentity, context)?;
+        let result = app.scripts()?.look_output(libentity, context)?;
         println!("{}", result.trim_end());
 
         Ok(())
@@ -0,0 +1,66 @@
+use crate::app::App;
+use crate::comps_interaction::{replace_tag_ancestor, tag_is_valid, tag_matches};
+use crate::entity_base::Tag;
+use crate::error_ext::ComError;
+
+use super::tags_pcmd::tag_counts;
+use super::{PCommand, PExecutionError};
+
+use itertools::Itertools;
+
+/// Replaces the source tag by the target one in all library entities. Descendants of the source
+/// tag are moved under the target one. Library entities that already have the target tag just
+/// lose the source one.
+#[derive(Debug, Clone)]
+pub struct MergeTagsPCMD {
+    source_tag: Tag,
+    target_tag: Tag,
+}
+
+impl MergeTagsPCMD {
+    pub fn new(source_tag: Tag, target_tag: Tag) -> Self {
+        MergeTagsPCMD {
+            source_tag,
+            target_tag,
+        }
+    }
+}
+
+impl PCommand for MergeTagsPCMD {
+    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
+        if !tag_is_valid(&self.target_tag) {
+            return Err(ComError::from(format!(
+                "tag '{}' mustn't be empty or contain spaces",
+                self.target_tag
+            ))
+            .into());
+        }
+        if self.source_tag == self.target_tag {
+            return Err(ComError::from("can't merge tag with itself".to_string()).into());
+        }
+
+        if !tag_counts(app.library())?
+            .keys()
+            .any(|tag| tag_matches(tag, &self.source_tag))
+        {
+            return Err(ComError::from(format!("couldn't find tag '{}'", self.source_tag)).into());
+        }
+
+        let changed_count = app.library_mut().rewrite_tags(|tags| {
+            tags.iter()
+                .map(|tag| {
+                    replace_tag_ancestor(tag, &self.source_tag, &self.target_tag)
+                        .unwrap_or_else(|| tag.clone())
+                })
+                .unique()
+                .collect()
+        })?;
+
+        println!(
+            "Tag '{}' was merged into '{}' in {} library entities",
+            self.source_tag, self.target_tag, changed_count
+        );
+
+        Ok(())
+    }
+}
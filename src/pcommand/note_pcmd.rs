@@ -0,0 +1,85 @@
+use crate::app::App;
+use crate::editor::edit_text;
+use crate::entity_selector::EntitySelector;
+use crate::error_ext::ComError;
+
+use super::{PCommand, PExecutionError};
+
+const NOTE_FILE_EXTENSION: &str = "md";
+const PARAGRAPH_SEPARATOR: &str = "\n\n";
+
+/// Returns `None` if the note is empty.
+fn normalize_note(note: &str) -> Option<String> {
+    match note.trim() {
+        "" => None,
+        note => Some(note.to_string()),
+    }
+}
+
+/// Edits the description of library entity as a multi-line Markdown note.
+#[derive(Debug, Clone)]
+pub struct NotePCMD {
+    entity: EntitySelector,
+    append: Option<String>,
+}
+
+impl NotePCMD {
+    pub fn new(entity: EntitySelector, append: Option<String>) -> Self {
+        NotePCMD { entity, append }
+    }
+
+    /// Returns new note. Appended text goes to a new paragraph, otherwise the note is opened in
+    /// the editor.
+    fn make_note(
+        &self,
+        old_note: Option<&String>,
+        id: String,
+    ) -> Result<Option<String>, PExecutionError> {
+        let new_note = match (&self.append, old_note) {
+            (Some(appended), Some(old_note)) => {
+                format!("{}{}{}", old_note, PARAGRAPH_SEPARATOR, appended)
+            }
+            (Some(appended), None) => appended.clone(),
+            (None, old_note) => edit_text(
+                old_note.map(|note| note.as_str()).unwrap_or_default(),
+                &id,
+                NOTE_FILE_EXTENSION,
+            )?,
+        };
+
+        Ok(normalize_note(&new_note))
+    }
+}
+
+impl PCommand for NotePCMD {
+    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
+        let path = self.entity.resolve_path(app.library())?;
+        let libentity = match app.library().get_libentity(path.clone())? {
+            Some(libentity) => libentity,
+            None => {
+                return Err(ComError::from(format!(
+                    "couldn't find library entity with path '{}'",
+                    path.to_string_lossy()
+                ))
+                .into());
+            }
+        };
+
+        let note = self.make_note(libentity.description(), libentity.id().to_string())?;
+        if note.as_ref() == libentity.description() {
+            println!("Nothing was changed");
+            return Ok(());
+        }
+
+        let is_deleted = note.is_none();
+        app.library_mut().set_description(libentity.id(), note)?;
+
+        if is_deleted {
+            println!("The note was deleted");
+        } else {
+            println!("The note was saved");
+        }
+
+        Ok(())
+    }
+}
@@ -1,14 +1,16 @@
+use super::hooks::{hook_scripts, progress_changed};
 use super::{PCommand, PExecutionError};
 
 use crate::app::App;
 use crate::comps_appearance::{progress_to_string, progress_update_from_string};
 use crate::comps_interaction::libentity_has_progress;
 use crate::entity_base::EntityType;
+use crate::entity_selector::EntitySelector;
 use crate::error_ext::ComError;
 use crate::progress_update::ProgressUpdate;
 
 use std::io::{stdin, stdout, Error as IoError, Write};
-use std::path::PathBuf;
+use std::path::Path;
 use std::process::Command;
 
 fn read_input_stdin() -> Result<String, IoError> {
@@ -27,19 +29,24 @@ fn write_stdout(string: &str) -> Result<(), IoError> {
 
 #[derive(Debug, Clone)]
 pub struct OpenPCMD {
-    path: PathBuf,
+    entity: EntitySelector,
     just_look: bool,
 }
 
 impl OpenPCMD {
-    pub fn new(path: PathBuf, just_look: bool) -> Self {
-        OpenPCMD { path, just_look }
+    pub fn new(entity: EntitySelector, just_look: bool) -> Self {
+        OpenPCMD { entity, just_look }
     }
 
-    fn reading_session(&self, viewer: &str, addit_args: &[String]) -> Result<(), PExecutionError> {
+    fn reading_session(
+        &self,
+        path: &Path,
+        viewer: &str,
+        addit_args: &[String],
+    ) -> Result<(), PExecutionError> {
         let exit_status = Command::new(viewer)
             .args(addit_args)
-            .arg(&self.path)
+            .arg(path)
             .spawn()
             .map_err(|e| ComError::from(format!("can't spawn viewer process: {e}")))?
             .wait()
@@ -70,7 +77,7 @@ impl OpenPCMD {
 
 impl PCommand for OpenPCMD {
     fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
-        let args = app.config().viewer();
+        let args = app.config()?.viewer();
         let viewer = match args.get(0) {
             Some(viewer) => viewer,
             None => {
@@ -81,12 +88,16 @@ impl PCommand for OpenPCMD {
         };
         let addit_args = &args[1..];
 
-        self.reading_session(viewer, addit_args)?;
+        let path = self.entity.resolve_path(app.library())?;
+        self.reading_session(&path, viewer, addit_args)?;
 
-        let libentity = match app.library().get_libentity(self.path.clone())? {
+        let libentity = match app.library().get_libentity(path)? {
             Some(libentity) => libentity,
             None => return Err(ComError::from(format!("couldn't find library entity")).into()),
         };
+        if let Some(scripts) = hook_scripts(app) {
+            scripts.on_open(libentity.clone());
+        }
         let libentity_etype = libentity.etype();
         let mut progress = match libentity.progress() {
             Some(progress) => progress.clone(),
@@ -98,9 +109,11 @@ impl PCommand for OpenPCMD {
             None => return Ok(()),
         };
 
+        let old_progress = progress;
         progress_update.execute_for(&mut progress)?;
 
         unsafe { app.library_mut().storage_mut() }.update_progress(libentity.id(), progress)?;
+        progress_changed(app, libentity.id(), old_progress, progress);
 
         println!(
             "The progress was updated to {}",
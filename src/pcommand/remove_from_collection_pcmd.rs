@@ -0,0 +1,35 @@
+use crate::app::App;
+use crate::entity_selector::EntitySelector;
+
+use super::{PCommand, PExecutionError};
+
+/// Removes the library entities from the collection. They stay in the library.
+#[derive(Debug, Clone)]
+pub struct RemoveFromCollectionPCMD {
+    name: String,
+    entities: Vec<EntitySelector>,
+}
+
+impl RemoveFromCollectionPCMD {
+    pub fn new(name: String, entities: Vec<EntitySelector>) -> Self {
+        RemoveFromCollectionPCMD { name, entities }
+    }
+}
+
+impl PCommand for RemoveFromCollectionPCMD {
+    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
+        let mut ids = Vec::with_capacity(self.entities.len());
+        for entity in &self.entities {
+            ids.push(entity.resolve_id(app.library())?);
+        }
+
+        let removed_count = app.library_mut().remove_from_collection(&self.name, &ids)?;
+
+        println!(
+            "{} library entities were removed from collection '{}'",
+            removed_count, self.name
+        );
+
+        Ok(())
+    }
+}
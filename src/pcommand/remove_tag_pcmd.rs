@@ -0,0 +1,40 @@
+use crate::app::App;
+use crate::comps_interaction::tag_matches;
+use crate::entity_base::Tag;
+use crate::error_ext::ComError;
+
+use super::{PCommand, PExecutionError};
+
+/// Removes the tag and its descendants from all library entities.
+#[derive(Debug, Clone)]
+pub struct RemoveTagPCMD {
+    tag: Tag,
+}
+
+impl RemoveTagPCMD {
+    pub fn new(tag: Tag) -> Self {
+        RemoveTagPCMD { tag }
+    }
+}
+
+impl PCommand for RemoveTagPCMD {
+    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
+        let changed_count = app.library_mut().rewrite_tags(|tags| {
+            tags.iter()
+                .filter(|tag| !tag_matches(tag, &self.tag))
+                .cloned()
+                .collect()
+        })?;
+
+        if changed_count == 0 {
+            return Err(ComError::from(format!("couldn't find tag '{}'", self.tag)).into());
+        }
+
+        println!(
+            "Tag '{}' was removed from {} library entities",
+            self.tag, changed_count
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use crate::app::App;
+use crate::comps_interaction::{replace_tag_ancestor, tag_is_valid, tag_matches};
+use crate::entity_base::Tag;
+use crate::error_ext::ComError;
+
+use super::tags_pcmd::tag_counts;
+use super::{PCommand, PExecutionError};
+
+/// Renames the tag and its descendants in all library entities: renaming `math` to `maths` turns
+/// `math/algebra` into `maths/algebra`. The new tag mustn't exist, use `MergeTagsPCMD` to join two
+/// existing tags.
+#[derive(Debug, Clone)]
+pub struct RenameTagPCMD {
+    old_tag: Tag,
+    new_tag: Tag,
+}
+
+impl RenameTagPCMD {
+    pub fn new(old_tag: Tag, new_tag: Tag) -> Self {
+        RenameTagPCMD { old_tag, new_tag }
+    }
+}
+
+impl PCommand for RenameTagPCMD {
+    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
+        if !tag_is_valid(&self.new_tag) {
+            return Err(ComError::from(format!(
+                "tag '{}' mustn't be empty or contain spaces",
+                self.new_tag
+            ))
+            .into());
+        }
+
+        let counts = tag_counts(app.library())?;
+        if !counts.keys().any(|tag| tag_matches(tag, &self.old_tag)) {
+            return Err(ComError::from(format!("couldn't find tag '{}'", self.old_tag)).into());
+        }
+        if counts.keys().any(|tag| tag_matches(tag, &self.new_tag)) {
+            return Err(ComError::from(format!(
+                "tag '{}' already exists, use 'tags merge' to join the tags",
+                self.new_tag
+            ))
+            .into());
+        }
+
+        let changed_count = app.library_mut().rewrite_tags(|tags| {
+            tags.iter()
+                .map(|tag| {
+                    replace_tag_ancestor(tag, &self.old_tag, &self.new_tag)
+                        .unwrap_or_else(|| tag.clone())
+                })
+                .collect()
+        })?;
+
+        println!(
+            "Tag '{}' was renamed to '{}' in {} library entities",
+            self.old_tag, self.new_tag, changed_count
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use crate::app::App;
+
+use super::{PCommand, PExecutionError};
+
+/// Runs the command defined in the scripts file as `commands.<name>`. The command gets the
+/// `popusk` global and the arguments, the string it returns is printed.
+#[derive(Debug, Clone)]
+pub struct RunPCMD {
+    name: String,
+    args: Vec<String>,
+}
+
+impl RunPCMD {
+    pub fn new(name: String, args: Vec<String>) -> Self {
+        RunPCMD { name, args }
+    }
+}
+
+impl PCommand for RunPCMD {
+    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
+        if let Some(output) = app.scripts()?.run_command(&self.name, self.args.clone())? {
+            println!("{}", output.trim_end());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use crate::app::App;
+use crate::comps_appearance::progress_to_string;
+use crate::library::LibraryError;
+
+use super::{PCommand, PExecutionError};
+
+/// Prints members of the collection in order. If no name is given, prints all collections with
+/// counts of their members.
+#[derive(Debug, Clone)]
+pub struct ShowCollectionPCMD {
+    name: Option<String>,
+}
+
+impl ShowCollectionPCMD {
+    pub fn new(name: Option<String>) -> Self {
+        ShowCollectionPCMD { name }
+    }
+
+    fn print_collections(&self, app: &App) -> Result<(), PExecutionError> {
+        let names = app.library().get_collection_names()?;
+        if names.is_empty() {
+            println!("There're no collections in the library");
+            return Ok(());
+        }
+
+        for name in names {
+            let members = app.library().get_collection(&name)?.unwrap_or_default();
+            println!("{} ({})", name, members.len());
+        }
+
+        Ok(())
+    }
+}
+
+impl PCommand for ShowCollectionPCMD {
+    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
+        let name = match &self.name {
+            Some(name) => name,
+            None => return self.print_collections(app),
+        };
+        let members = match app.library().get_collection(name)? {
+            Some(members) => members,
+            None => return Err(LibraryError::CouldNotFindCollection { name: name.clone() }.into()),
+        };
+
+        if members.is_empty() {
+            println!("Collection '{}' is empty", name);
+            return Ok(());
+        }
+
+        let index_width = members.len().to_string().len();
+        for (index, id) in members.into_iter().enumerate() {
+            let libentity = match app.library().get_libentity_by_id(id)? {
+                Some(libentity) => libentity,
+                None => continue,
+            };
+            let progress = libentity
+                .progress()
+                .map(|progress| format!(" ({})", progress_to_string(progress)))
+                .unwrap_or_default();
+
+            println!(
+                "{:>width$}. #{} {}{}",
+                index + 1,
+                id,
+                libentity.name(),
+                progress,
+                width = index_width
+            );
+        }
+
+        Ok(())
+    }
+}
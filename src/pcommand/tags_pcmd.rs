@@ -0,0 +1,109 @@
+use crate::app::App;
+use crate::comps_interaction::tag_segments;
+use crate::entity_base::Tag;
+use crate::library::{Library, LibraryError};
+
+use super::{PCommand, PExecutionError};
+
+use std::collections::{BTreeMap, BTreeSet};
+
+const TREE_INDENT: &str = "  ";
+
+/// Returns all tags of the library with counts of library entities having them.
+pub(super) fn tag_counts(library: &Library) -> Result<BTreeMap<Tag, usize>, LibraryError> {
+    let mut counts = BTreeMap::new();
+
+    for base in library.get_bases()? {
+        for tag in base.tags() {
+            *counts.entry(tag.clone()).or_insert(0) += 1;
+        }
+    }
+
+    Ok(counts)
+}
+
+/// Returns all nodes of the tag hierarchy as lists of segments with counts of library entities
+/// having the node or its descendants. Lists of segments are ordered so that each node goes right
+/// before its descendants.
+fn tag_tree_counts(library: &Library) -> Result<BTreeMap<Vec<String>, usize>, LibraryError> {
+    let mut counts = BTreeMap::new();
+
+    for base in library.get_bases()? {
+        // A library entity is counted once even if it has several tags of the subtree.
+        let mut nodes = BTreeSet::new();
+        for tag in base.tags() {
+            let segments = tag_segments(tag);
+            for depth in 1..=segments.len() {
+                nodes.insert(
+                    segments[..depth]
+                        .iter()
+                        .map(|segment| segment.to_string())
+                        .collect::<Vec<_>>(),
+                );
+            }
+        }
+
+        for node in nodes {
+            *counts.entry(node).or_insert(0) += 1;
+        }
+    }
+
+    Ok(counts)
+}
+
+/// Prints all tags of the library with counts of library entities having them. In the tree mode
+/// hierarchical tags are shown as a tree.
+#[derive(Debug, Clone)]
+pub struct TagsPCMD {
+    tree: bool,
+}
+
+impl TagsPCMD {
+    pub fn new(tree: bool) -> Self {
+        TagsPCMD { tree }
+    }
+
+    fn print_tree(&self, app: &App) -> Result<(), PExecutionError> {
+        for (node, count) in tag_tree_counts(app.library())? {
+            println!(
+                "{}{} ({})",
+                TREE_INDENT.repeat(node.len() - 1),
+                node[node.len() - 1],
+                count
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl PCommand for TagsPCMD {
+    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
+        if self.tree {
+            return self.print_tree(app);
+        }
+
+        let counts = tag_counts(app.library())?;
+        if counts.is_empty() {
+            println!("There're no tags in the library");
+            return Ok(());
+        }
+
+        // The most used tags go first, tags with the same count are sorted by name.
+        let mut counts = counts.into_iter().collect::<Vec<_>>();
+        counts.sort_by(|(tag_a, count_a), (tag_b, count_b)| {
+            count_b.cmp(count_a).then_with(|| tag_a.cmp(tag_b))
+        });
+
+        let tag_width = counts
+            .iter()
+            .map(|(tag, _)| tag.chars().count())
+            .max()
+            .unwrap_or(0);
+        for (tag, count) in counts {
+            println!("{:<width$}  {}", tag, count, width = tag_width);
+        }
+
+        Ok(())
+    }
+}
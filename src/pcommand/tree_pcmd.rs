@@ -0,0 +1,126 @@
+use crate::app::App;
+use crate::comps_appearance::progress_to_string;
+use crate::entity_base::EntityType;
+use crate::entity_selector::EntitySelector;
+use crate::error_ext::ComError;
+use crate::library_tree::LibraryTree;
+use crate::scripts::Context;
+
+use super::{PCommand, PExecutionError};
+
+const BRANCH: &str = "├── ";
+const LAST_BRANCH: &str = "└── ";
+const INDENT: &str = "│   ";
+const LAST_INDENT: &str = "    ";
+
+fn node_label(node: &LibraryTree) -> String {
+    let libentity = match &node.libentity {
+        Some(libentity) => libentity,
+        None => return format!("{}/ (untracked)", node.name),
+    };
+
+    match libentity.etype() {
+        EntityType::Section => match libentity.aggregate_progress() {
+            Some(aggregate_progress) if aggregate_progress.documents > 0 => format!(
+                "{}/ [{}/{}, {}/{} documents]",
+                libentity.name(),
+                aggregate_progress.passed,
+                aggregate_progress.ceiling,
+                aggregate_progress.finished_documents,
+                aggregate_progress.documents
+            ),
+            _ => format!("{}/", libentity.name()),
+        },
+        _ => match libentity.progress() {
+            Some(progress) => format!("{} [{}]", libentity.name(), progress_to_string(progress)),
+            None => libentity.name().clone(),
+        },
+    }
+}
+
+fn render_children(node: &LibraryTree, prefix: &str, lines: &mut Vec<String>) {
+    for (index, child) in node.children.iter().enumerate() {
+        let is_last = index + 1 == node.children.len();
+        let (branch, indent) = if is_last {
+            (LAST_BRANCH, LAST_INDENT)
+        } else {
+            (BRANCH, INDENT)
+        };
+
+        lines.push(format!("{}{}{}", prefix, branch, node_label(child)));
+        render_children(child, &format!("{}{}", prefix, indent), lines);
+    }
+}
+
+/// Output of the `tree` command when the scripts file doesn't define `tree_output`.
+fn default_tree_output(tree: &LibraryTree) -> String {
+    // The root of the library isn't a directory of the library.
+    let root_label = if tree.is_tracked() {
+        node_label(tree)
+    } else {
+        tree.name.clone()
+    };
+    let mut lines = vec![root_label];
+    render_children(tree, "", &mut lines);
+
+    lines.join("\n")
+}
+
+/// Prints library entities nested under their parent sections. Untracked directories between them
+/// are shown too. If the section is given, only library entities beneath it are shown.
+#[derive(Debug, Clone)]
+pub struct TreePCMD {
+    section: Option<EntitySelector>,
+}
+
+impl TreePCMD {
+    pub fn new(section: Option<EntitySelector>) -> Self {
+        TreePCMD { section }
+    }
+}
+
+impl PCommand for TreePCMD {
+    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
+        let section = match &self.section {
+            Some(section) => {
+                let id = section.resolve_id(app.library())?;
+                match app.library().get_libentity_by_id(id)? {
+                    Some(libentity) if libentity.etype() == EntityType::Section => Some(libentity),
+                    Some(libentity) => {
+                        return Err(ComError::from(format!(
+                            "library entity '{}' isn't a section",
+                            libentity.name()
+                        ))
+                        .into())
+                    }
+                    None => {
+                        return Err(ComError::from(format!(
+                            "couldn't find library entity with ID {}",
+                            id
+                        ))
+                        .into())
+                    }
+                }
+            }
+            None => None,
+        };
+        let tree = LibraryTree::build(section, app.library().get_libentities()?);
+
+        let context = match Context::auto() {
+            Some(context) => context,
+            None => {
+                return Err(
+                    ComError::from("couldn't make context (Context object)".to_string()).into(),
+                )
+            }
+        };
+
+        let result = match app.scripts()?.tree_output(tree.clone(), context)? {
+            Some(result) => result,
+            None => default_tree_output(&tree),
+        };
+        println!("{}", result.trim_end());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use crate::app::App;
+use crate::entity_selector::EntitySelector;
+use crate::error_ext::ComError;
+use crate::relation::Relation;
+
+use super::{PCommand, PExecutionError};
+
+/// Removes the relation going from the source library entity to the target one.
+#[derive(Debug, Clone)]
+pub struct UnlinkPCMD {
+    source: EntitySelector,
+    relation: String,
+    target: EntitySelector,
+}
+
+impl UnlinkPCMD {
+    pub fn new(source: EntitySelector, relation: String, target: EntitySelector) -> Self {
+        UnlinkPCMD {
+            source,
+            relation,
+            target,
+        }
+    }
+}
+
+impl PCommand for UnlinkPCMD {
+    fn execute(&self, app: &mut App) -> Result<(), PExecutionError> {
+        let source_id = self.source.resolve_id(app.library())?;
+        let target_id = self.target.resolve_id(app.library())?;
+
+        let relation = Relation {
+            kind: self.relation.clone(),
+            target: target_id,
+        };
+        if !app.library_mut().remove_relation(source_id, &relation)? {
+            return Err(ComError::from(format!(
+                "there's no relation #{} {} #{}",
+                source_id, self.relation, target_id
+            ))
+            .into());
+        }
+
+        println!("The relation was removed");
+
+        Ok(())
+    }
+}
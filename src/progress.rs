@@ -34,6 +34,11 @@ impl Progress {
         self.ceiling
     }
 
+    /// Returns `true` if the document is read up to the ceiling.
+    pub fn is_finished(&self) -> bool {
+        self.passed == self.ceiling
+    }
+
     /// Returns error if new passed value is invalid (if it brakes the invariants)
     pub fn set_passed(&mut self, new_passed: usize) {
         if new_passed > self.ceiling {
@@ -51,3 +56,27 @@ impl Progress {
         self.passed = self.passed.checked_sub(by).unwrap_or(0);
     }
 }
+
+/// Progress of all documents beneath a section. It's computed from progresses of the documents
+/// and isn't stored, so sections still don't have their own progress.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AggregateProgress {
+    /// Sum of passed pages of the documents.
+    pub passed: usize,
+    /// Sum of ceilings of the documents.
+    pub ceiling: usize,
+    pub documents: usize,
+    pub finished_documents: usize,
+}
+
+impl AggregateProgress {
+    pub fn add(&mut self, progress: &Progress) {
+        self.passed += progress.passed();
+        self.ceiling += progress.ceiling();
+        self.documents += 1;
+
+        if progress.is_finished() {
+            self.finished_documents += 1;
+        }
+    }
+}
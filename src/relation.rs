@@ -0,0 +1,23 @@
+//! Here are typed directional relations between library entities: a document may be a
+//! translation of, a newer edition of or a solution manual for another one. Kinds of relations
+//! aren't fixed.
+
+use crate::id::ID;
+
+use serde_derive::{Deserialize, Serialize};
+
+/// Kinds of relations consist of ASCII letters, digits, `_` and `-`.
+pub fn relation_kind_is_valid(kind: &str) -> bool {
+    !kind.is_empty()
+        && kind
+            .chars()
+            .all(|symbol| symbol.is_ascii_alphanumeric() || symbol == '_' || symbol == '-')
+}
+
+/// Relation of the library entity to the target one, e.g. `<entity> translation_of <target>`.
+/// Relations are kept by the library entity they go from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Relation {
+    pub kind: String,
+    pub target: ID,
+}
@@ -1,13 +1,23 @@
+use crate::app::WORKING_DIR;
+use crate::bibliography::Bibliography;
 use crate::comps_appearance::entitytype_to_string;
+use crate::comps_interaction::tag_segments;
+use crate::config::Config;
+use crate::entity_base::EntityType;
 use crate::global_conf_directory::{configdir, GlobalConfError};
-use crate::libentity::LibEntity;
+use crate::id::ID;
+use crate::libentity::{LibEntity, LibEntityData, LinkedEntity};
+use crate::library_tree::LibraryTree;
+use crate::lua_library::{LuaLibrary, PopuskModule};
+use crate::progress::{AggregateProgress, Progress};
 
+use std::collections::HashMap;
 use std::io::{Error as IoError, ErrorKind as IoErrorKind};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use mlua::{
-    Error as LuaError, Function as LuaFunction, IntoLua, Lua, LuaOptions, Result as LuaResult,
-    StdLib, Value as LuaValue,
+    Error as LuaError, Function as LuaFunction, IntoLua, IntoLuaMulti, Lua, LuaOptions,
+    Result as LuaResult, StdLib, Table as LuaTable, Value as LuaValue,
 };
 use thiserror::Error;
 
@@ -15,14 +25,29 @@ use thiserror::Error;
 pub enum ScriptsError {
     #[error("global conf: {0}")]
     GlobalConfError(#[from] GlobalConfError),
-    #[error("couldn't find scripts file in the '{0}' path")]
-    ScriptsFileWasNotFound(PathBuf),
     #[error("lua runtime: {0}")]
     LuaRuntimeError(String),
+    #[error("lua syntax: {0}")]
+    LuaSyntaxError(String),
+    #[error("scripts file '{path}' doesn't define the function '{name}'")]
+    FunctionIsNotDefined { name: String, path: PathBuf },
+    #[error("the function returned {returned} instead of {expected}")]
+    WrongReturnType {
+        expected: &'static str,
+        returned: &'static str,
+    },
     #[error("lua error: {0}")]
     LuaError(#[from] LuaError),
+    #[error("there's no command '{0}' in the scripts file")]
+    UnknownCommand(String),
+    #[error("there's no sorter '{0}' in the scripts file")]
+    UnknownSorter(String),
+    #[error("there's no filter '{0}' in the scripts file")]
+    UnknownFilter(String),
     #[error("io error with scripts file: {0}")]
     IOErrorWithScriptsFile(IoError),
+    #[error("'{file}' replaced the table '{registry}', so functions of earlier files are lost; add functions to the table instead")]
+    RegistryReplaced { registry: String, file: String },
 }
 
 #[derive(Debug, Clone)]
@@ -56,6 +81,122 @@ impl IntoLua for Context {
     }
 }
 
+fn linked_entities_to_lua(lua: &Lua, linked_entities: &[LinkedEntity]) -> LuaResult<LuaTable> {
+    let linked_entities_table = lua.create_table()?;
+
+    for linked_entity in linked_entities {
+        let linked_entity_table = lua.create_table()?;
+
+        linked_entity_table.set("relation", linked_entity.relation.clone())?;
+        linked_entity_table.set("id", linked_entity.id.to_string())?;
+        linked_entity_table.set("name", linked_entity.name.clone())?;
+
+        linked_entities_table.push(linked_entity_table)?;
+    }
+
+    Ok(linked_entities_table)
+}
+
+/// Sets computed fields (`fields.<name>(libentity)`) of the scripts file to the library entity
+/// table. Computed fields can't replace fields of the library entity.
+fn set_computed_fields(lua: &Lua, libentity_table: &LuaTable) -> LuaResult<()> {
+    let fields = match lua.globals().get::<Option<LuaTable>>("fields")? {
+        Some(fields) => fields,
+        None => return Ok(()),
+    };
+
+    for pair in fields.pairs::<String, LuaFunction>() {
+        let (name, field_func) = pair?;
+        if libentity_table.contains_key(name.as_str())? {
+            return Err(LuaError::RuntimeError(format!(
+                "computed field '{}' collides with the field of library entity",
+                name
+            )));
+        }
+
+        let value = field_func.call::<LuaValue>(libentity_table.clone())?;
+        libentity_table.set(name, value)?;
+    }
+
+    Ok(())
+}
+
+/// Hooks called on changes of the library. They aren't called by `Scripts::check`, cause they may
+/// have side effects.
+const HOOKS: &[&str] = &["on_add", "on_delete", "on_progress", "on_finish", "on_open"];
+
+/// Returns library entities used for checking the scripts file: a section with a document and a
+/// regular file inside of it.
+fn sample_libentities() -> Vec<LibEntity> {
+    let progress = Progress::with_passed(10, 100);
+    let document = LibEntity::from_id_data(
+        ID::new(2),
+        LibEntityData {
+            path: PathBuf::from("samples/book.pdf"),
+            name: "Sample book".to_string(),
+            etype: EntityType::Document,
+            tags: vec!["sample".to_string(), "sample/book".to_string()],
+            progress: Some(progress),
+            description: Some("Sample description.\nThe second line.".to_string()),
+            bibliography: Some(Bibliography {
+                authors: vec!["Sample Author".to_string()],
+                year: Some(2000),
+                ..Default::default()
+            }),
+        },
+    );
+    let regular = LibEntity::from_id_data(
+        ID::new(3),
+        LibEntityData {
+            path: PathBuf::from("samples/notes.txt"),
+            name: "notes".to_string(),
+            etype: EntityType::Regular,
+            tags: Vec::new(),
+            progress: None,
+            description: None,
+            bibliography: None,
+        },
+    );
+    let mut section = LibEntity::from_id_data(
+        ID::new(1),
+        LibEntityData {
+            path: PathBuf::from("samples"),
+            name: "samples".to_string(),
+            etype: EntityType::Section,
+            tags: vec!["sample".to_string()],
+            progress: None,
+            description: None,
+            bibliography: None,
+        },
+    );
+    let mut aggregate_progress = AggregateProgress::default();
+    aggregate_progress.add(&progress);
+    section.set_aggregate_progress(Some(aggregate_progress));
+
+    vec![section, document, regular]
+}
+
+fn check_return_type(value: LuaValue, expected: &'static str) -> Result<(), ScriptsError> {
+    match value.type_name() {
+        returned if returned == expected => Ok(()),
+        returned => Err(ScriptsError::WrongReturnType { expected, returned }),
+    }
+}
+
+/// Converts Lua errors to errors of scripts. Messages of runtime and syntax errors start with the
+/// path of the scripts file and the line, runtime errors contain the traceback.
+fn scripts_error(lua_error: LuaError) -> ScriptsError {
+    match lua_error {
+        LuaError::RuntimeError(runtime_err_msg) => ScriptsError::LuaRuntimeError(runtime_err_msg),
+        LuaError::SyntaxError { message, .. } => ScriptsError::LuaSyntaxError(message),
+        // Errors of Rust functions called from scripts (e.g. `popusk.get`).
+        LuaError::CallbackError { traceback, cause } => {
+            ScriptsError::LuaRuntimeError(format!("{}\n{}", cause, traceback))
+        }
+        lua_error => lua_error.into(),
+    }
+}
+
 impl IntoLua for LibEntity {
     fn into_lua(self, lua: &Lua) -> LuaResult<LuaValue> {
         let libentity_table = lua.create_table()?;
@@ -64,46 +205,149 @@ impl IntoLua for LibEntity {
         libentity_table.set("id", self.id().to_string())?;
         libentity_table.set("name", self.name().clone())?;
         libentity_table.set("tags", self.tags().clone())?;
+        libentity_table.set(
+            "tag_segments",
+            self.tags()
+                .iter()
+                .map(|tag| {
+                    tag_segments(tag)
+                        .into_iter()
+                        .map(|segment| segment.to_string())
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>(),
+        )?;
         libentity_table.set("etype", entitytype_to_string(self.etype()))?;
+        libentity_table.set("collections", self.collections().clone())?;
+        libentity_table.set("links", linked_entities_to_lua(lua, self.links())?)?;
+        libentity_table.set("backlinks", linked_entities_to_lua(lua, self.backlinks())?)?;
 
         if let Some(progress) = self.progress() {
-            let progress_table = lua.create_table()?;
+            libentity_table.set("progress", *progress)?;
+        }
+
+        if let Some(aggregate_progress) = self.aggregate_progress() {
+            let aggregate_progress_table = lua.create_table()?;
 
-            progress_table.set("passed", progress.passed())?;
-            progress_table.set("ceiling", progress.ceiling())?;
+            aggregate_progress_table.set("passed", aggregate_progress.passed)?;
+            aggregate_progress_table.set("ceiling", aggregate_progress.ceiling)?;
+            aggregate_progress_table.set("documents", aggregate_progress.documents)?;
+            aggregate_progress_table
+                .set("finished_documents", aggregate_progress.finished_documents)?;
 
-            libentity_table.set("progress", progress_table)?;
+            libentity_table.set("aggregate_progress", aggregate_progress_table)?;
         }
 
         if let Some(description) = self.description() {
             libentity_table.set("description", description.clone())?;
+            libentity_table.set(
+                "description_lines",
+                description
+                    .lines()
+                    .map(|line| line.to_string())
+                    .collect::<Vec<_>>(),
+            )?;
         }
 
+        if let Some(bibliography) = self.bibliography() {
+            let bibliography_table = lua.create_table()?;
+
+            bibliography_table.set("authors", bibliography.authors.clone())?;
+            bibliography_table.set("year", bibliography.year)?;
+            bibliography_table.set("publisher", bibliography.publisher.clone())?;
+            bibliography_table.set("isbn", bibliography.isbn.clone())?;
+            bibliography_table.set("doi", bibliography.doi.clone())?;
+            bibliography_table.set("language", bibliography.language.clone())?;
+            bibliography_table.set("edition", bibliography.edition.clone())?;
+            bibliography_table.set("series", bibliography.series.clone())?;
+            bibliography_table.set("series_index", bibliography.series_index)?;
+
+            libentity_table.set("bibliography", bibliography_table)?;
+        }
+
+        set_computed_fields(lua, &libentity_table)?;
+
         Ok(LuaValue::Table(libentity_table))
     }
 }
 
+impl IntoLua for Progress {
+    fn into_lua(self, lua: &Lua) -> LuaResult<LuaValue> {
+        let progress_table = lua.create_table()?;
+
+        progress_table.set("passed", self.passed())?;
+        progress_table.set("ceiling", self.ceiling())?;
+
+        Ok(LuaValue::Table(progress_table))
+    }
+}
+
+impl IntoLua for Config {
+    fn into_lua(self, lua: &Lua) -> LuaResult<LuaValue> {
+        let config_table = lua.create_table()?;
+
+        config_table.set("document_extension", self.document_extension().to_vec())?;
+        config_table.set("viewer", self.viewer().to_vec())?;
+        config_table.set("scripts_modify_library", self.scripts_modify_library())?;
+
+        Ok(LuaValue::Table(config_table))
+    }
+}
+
+impl IntoLua for LibraryTree {
+    fn into_lua(self, lua: &Lua) -> LuaResult<LuaValue> {
+        let tree_table = lua.create_table()?;
+
+        tree_table.set("name", self.name.clone())?;
+        tree_table.set("path", self.path.to_string_lossy())?;
+        tree_table.set("tracked", self.is_tracked())?;
+        tree_table.set("is_directory", self.is_directory())?;
+        if let Some(libentity) = self.libentity {
+            tree_table.set("libentity", libentity)?;
+        }
+        tree_table.set("children", self.children)?;
+
+        Ok(LuaValue::Table(tree_table))
+    }
+}
+
 #[derive(Debug)]
 // INVARIATNS:
 // - `lua` field is used only for getting variables.
 pub struct Scripts {
     lua: Lua,
+    path: PathBuf,
 }
 
 impl Scripts {
+    /// Makes the library available for the `popusk` global.
+    pub fn set_library(&self, library: LuaLibrary) {
+        self.lua.set_app_data(library);
+    }
+
+    /// Returns the global function of the scripts file, `None` if it isn't defined.
+    fn optional_function(&self, name: &str) -> Result<Option<LuaFunction>, ScriptsError> {
+        Ok(self.lua.globals().get::<Option<LuaFunction>>(name)?)
+    }
+
+    fn required_function(&self, name: &str) -> Result<LuaFunction, ScriptsError> {
+        match self.optional_function(name)? {
+            Some(func) => Ok(func),
+            None => Err(ScriptsError::FunctionIsNotDefined {
+                name: name.to_string(),
+                path: self.path.clone(),
+            }),
+        }
+    }
+
     pub fn look_output(
         &self,
         libentity: LibEntity,
         context: Context,
     ) -> Result<String, ScriptsError> {
-        let look_output_func = self.lua.globals().get::<LuaFunction>("look_output")?;
-        match look_output_func.call::<String>((libentity, context)) {
-            Ok(string) => Ok(string),
-            Err(LuaError::RuntimeError(runtime_err_msg)) => {
-                return Err(ScriptsError::LuaRuntimeError(runtime_err_msg))
-            }
-            Err(lua_error) => return Err(lua_error.into()),
-        }
+        self.required_function("look_output")?
+            .call::<String>((libentity, context))
+            .map_err(scripts_error)
     }
 
     pub fn list_output_narrow(
@@ -111,17 +355,9 @@ impl Scripts {
         libentities: Vec<LibEntity>,
         context: Context,
     ) -> Result<String, ScriptsError> {
-        let list_output_narrow_func = self
-            .lua
-            .globals()
-            .get::<LuaFunction>("list_output_narrow")?;
-        match list_output_narrow_func.call::<String>((libentities, context)) {
-            Ok(string) => Ok(string),
-            Err(LuaError::RuntimeError(runtime_err_msg)) => {
-                return Err(ScriptsError::LuaRuntimeError(runtime_err_msg))
-            }
-            Err(lua_error) => return Err(lua_error.into()),
-        }
+        self.required_function("list_output_narrow")?
+            .call::<String>((libentities, context))
+            .map_err(scripts_error)
     }
 
     pub fn list_output_wide(
@@ -129,34 +365,433 @@ impl Scripts {
         libentities: Vec<LibEntity>,
         context: Context,
     ) -> Result<String, ScriptsError> {
-        let list_output_wide_func = self.lua.globals().get::<LuaFunction>("list_output_wide")?;
-        match list_output_wide_func.call::<String>((libentities, context)) {
-            Ok(string) => Ok(string),
-            Err(LuaError::RuntimeError(runtime_err_msg)) => {
-                return Err(ScriptsError::LuaRuntimeError(runtime_err_msg))
+        self.required_function("list_output_wide")?
+            .call::<String>((libentities, context))
+            .map_err(scripts_error)
+    }
+
+    /// Calls the hook if the scripts file defines it. Errors of the hook are reported, but they
+    /// don't stop the command that called the hook, cause its changes are already saved.
+    fn call_hook(&self, name: &str, args: impl IntoLuaMulti) {
+        let result = match self.optional_function(name) {
+            Ok(Some(hook_func)) => hook_func.call::<()>(args).map_err(scripts_error),
+            Ok(None) => Ok(()),
+            Err(err) => Err(err),
+        };
+
+        if let Err(err) = result {
+            eprintln!("Hook '{}' failed: {}", name, err);
+        }
+    }
+
+    /// Called after the library entity is added.
+    pub fn on_add(&self, libentity: LibEntity) {
+        self.call_hook("on_add", libentity)
+    }
+
+    /// Called after the library entity is deleted, so the library doesn't contain it anymore.
+    pub fn on_delete(&self, libentity: LibEntity) {
+        self.call_hook("on_delete", libentity)
+    }
+
+    /// Called after the progress of the document is changed. `libentity` has the new progress.
+    pub fn on_progress(
+        &self,
+        libentity: LibEntity,
+        old_progress: Progress,
+        new_progress: Progress,
+    ) {
+        self.call_hook("on_progress", (libentity, old_progress, new_progress))
+    }
+
+    /// Called after the progress of the document reaches its ceiling.
+    pub fn on_finish(&self, libentity: LibEntity) {
+        self.call_hook("on_finish", libentity)
+    }
+
+    /// Called after the viewer of the library entity is closed.
+    pub fn on_open(&self, libentity: LibEntity) {
+        self.call_hook("on_open", libentity)
+    }
+
+    /// Returns `true` if the scripts file defines `commands.<name>`.
+    pub fn has_command(&self, name: &str) -> Result<bool, ScriptsError> {
+        Ok(self.registered_function("commands", name)?.is_some())
+    }
+
+    /// Calls `commands.<name>(popusk, args)`. Returns the string returned by the command, the
+    /// command may return nothing.
+    pub fn run_command(
+        &self,
+        name: &str,
+        args: Vec<String>,
+    ) -> Result<Option<String>, ScriptsError> {
+        let command_func = match self.registered_function("commands", name)? {
+            Some(command_func) => command_func,
+            None => return Err(ScriptsError::UnknownCommand(name.to_string())),
+        };
+
+        let popusk = self.lua.globals().get::<LuaValue>("popusk")?;
+        command_func
+            .call::<Option<String>>((popusk, args))
+            .map_err(scripts_error)
+    }
+
+    /// Returns names of functions of the registry (e.g. `sorters`) in alphabetical order.
+    fn registered_names(&self, registry: &str) -> Result<Vec<String>, ScriptsError> {
+        let mut names = Vec::new();
+        if let Some(functions) = self.lua.globals().get::<Option<LuaTable>>(registry)? {
+            for pair in functions.pairs::<String, LuaFunction>() {
+                names.push(pair?.0);
+            }
+        }
+        names.sort();
+
+        Ok(names)
+    }
+
+    /// Calls functions of the scripts file with sample data and checks their results. Hooks and
+    /// commands are only checked to be functions. Returns results of the checks by names of the
+    /// functions.
+    pub fn check(&self, context: Context) -> Vec<(String, Result<(), ScriptsError>)> {
+        let libentities = sample_libentities();
+        let document = libentities[1].clone();
+        let tree = LibraryTree::build(None, libentities.clone());
+
+        let mut checks = vec![
+            (
+                "fields".to_string(),
+                // Computed fields are set while converting.
+                document
+                    .clone()
+                    .into_lua(&self.lua)
+                    .map(|_| ())
+                    .map_err(scripts_error),
+            ),
+            (
+                "look_output".to_string(),
+                self.required_function("look_output").and_then(|func| {
+                    check_return_type(
+                        func.call::<LuaValue>((document.clone(), context.clone()))
+                            .map_err(scripts_error)?,
+                        "string",
+                    )
+                }),
+            ),
+        ];
+        for name in ["list_output_narrow", "list_output_wide"] {
+            let result = self.required_function(name).and_then(|func| {
+                check_return_type(
+                    func.call::<LuaValue>((libentities.clone(), context.clone()))
+                        .map_err(scripts_error)?,
+                    "string",
+                )
+            });
+            checks.push((name.to_string(), result));
+        }
+        match self.optional_function("tree_output") {
+            Ok(Some(func)) => checks.push((
+                "tree_output".to_string(),
+                func.call::<LuaValue>((tree, context))
+                    .map_err(scripts_error)
+                    .and_then(|value| check_return_type(value, "string")),
+            )),
+            Ok(None) => (),
+            Err(err) => checks.push(("tree_output".to_string(), Err(err))),
+        }
+        // Hooks may modify the library, so they're called in the dry run.
+        self.set_dry_run(true);
+        let unfinished = Progress::with_passed(10, 100);
+        let finished = Progress::with_passed(100, 100);
+        let mut finished_document = document.clone();
+        finished_document.set_progress(Some(finished));
+        for hook in HOOKS {
+            let func = match self.optional_function(hook) {
+                Ok(Some(func)) => func,
+                Ok(None) => continue,
+                Err(err) => {
+                    checks.push((hook.to_string(), Err(err)));
+                    continue;
+                }
+            };
+
+            let result = match *hook {
+                "on_progress" => func.call::<()>((finished_document.clone(), unfinished, finished)),
+                "on_finish" => func.call::<()>(finished_document.clone()),
+                _ => func.call::<()>(document.clone()),
+            };
+            checks.push((hook.to_string(), result.map_err(scripts_error)));
+        }
+        self.set_dry_run(false);
+
+        for registry in ["commands", "sorters", "filters"] {
+            let names = match self.registered_names(registry) {
+                Ok(names) => names,
+                Err(err) => {
+                    checks.push((registry.to_string(), Err(err)));
+                    continue;
+                }
+            };
+
+            for name in names {
+                let result = match registry {
+                    "sorters" => self.check_registered_function(
+                        registry,
+                        &name,
+                        (document.clone(), libentities[2].clone()),
+                        "boolean",
+                    ),
+                    "filters" => {
+                        self.check_registered_function(registry, &name, document.clone(), "boolean")
+                    }
+                    _ => Ok(()),
+                };
+                checks.push((format!("{}.{}", registry, name), result));
             }
-            Err(lua_error) => return Err(lua_error.into()),
+        }
+
+        checks
+    }
+
+    fn set_dry_run(&self, dry_run: bool) {
+        if let Some(mut library) = self.lua.app_data_mut::<LuaLibrary>() {
+            library.set_dry_run(dry_run);
+        }
+    }
+
+    fn check_registered_function(
+        &self,
+        registry: &str,
+        name: &str,
+        args: impl IntoLuaMulti,
+        expected: &'static str,
+    ) -> Result<(), ScriptsError> {
+        // Names are taken from the registry, so the function exists.
+        if let Some(func) = self.registered_function(registry, name)? {
+            check_return_type(
+                func.call::<LuaValue>(args).map_err(scripts_error)?,
+                expected,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the function `<registry>.<name>` of the scripts file, e.g. `sorters.by_author`.
+    fn registered_function(
+        &self,
+        registry: &str,
+        name: &str,
+    ) -> Result<Option<LuaFunction>, ScriptsError> {
+        match self.lua.globals().get::<Option<LuaTable>>(registry)? {
+            Some(functions) => Ok(functions.get::<Option<LuaFunction>>(name)?),
+            None => Ok(None),
+        }
+    }
+
+    /// Keeps library entities for which `filters.<name>(libentity)` returns `true`.
+    pub fn filter_libentities(
+        &self,
+        name: &str,
+        libentities: Vec<LibEntity>,
+    ) -> Result<Vec<LibEntity>, ScriptsError> {
+        let filter_func = match self.registered_function("filters", name)? {
+            Some(filter_func) => filter_func,
+            None => return Err(ScriptsError::UnknownFilter(name.to_string())),
+        };
+
+        let mut filtered = Vec::with_capacity(libentities.len());
+        for libentity in libentities {
+            if filter_func
+                .call::<bool>(libentity.clone())
+                .map_err(scripts_error)?
+            {
+                filtered.push(libentity);
+            }
+        }
+
+        Ok(filtered)
+    }
+
+    /// Sorts library entities by `table.sort` with the comparator `sorters.<name>(a, b)` returning
+    /// `true` if `a` goes before `b`.
+    pub fn sort_libentities(
+        &self,
+        name: &str,
+        libentities: Vec<LibEntity>,
+    ) -> Result<Vec<LibEntity>, ScriptsError> {
+        let sorter_func = match self.registered_function("sorters", name)? {
+            Some(sorter_func) => sorter_func,
+            None => return Err(ScriptsError::UnknownSorter(name.to_string())),
+        };
+
+        let libentity_tables = self.lua.create_sequence_from(libentities.iter().cloned())?;
+        let sort_func = self
+            .lua
+            .globals()
+            .get::<LuaTable>("table")?
+            .get::<LuaFunction>("sort")?;
+        sort_func
+            .call::<()>((libentity_tables.clone(), sorter_func))
+            .map_err(scripts_error)?;
+
+        // Sorted tables are matched with library entities by IDs.
+        let mut libentities_by_id = libentities
+            .into_iter()
+            .map(|libentity| (libentity.id().to_string(), libentity))
+            .collect::<HashMap<_, _>>();
+        let mut sorted = Vec::with_capacity(libentities_by_id.len());
+        for libentity_table in libentity_tables.sequence_values::<LuaTable>() {
+            let id = libentity_table?.get::<String>("id")?;
+            match libentities_by_id.remove(&id) {
+                Some(libentity) => sorted.push(libentity),
+                None => {
+                    return Err(ScriptsError::LuaRuntimeError(format!(
+                        "sorter '{}' changed ID of library entity to '{}'",
+                        name, id
+                    )))
+                }
+            }
+        }
+
+        Ok(sorted)
+    }
+
+    /// Returns `None` if the scripts file doesn't define `tree_output`, so the default output is
+    /// used.
+    pub fn tree_output(
+        &self,
+        tree: LibraryTree,
+        context: Context,
+    ) -> Result<Option<String>, ScriptsError> {
+        match self.optional_function("tree_output")? {
+            Some(tree_output_func) => tree_output_func
+                .call::<String>((tree, context))
+                .map(Some)
+                .map_err(scripts_error),
+            None => Ok(None),
         }
     }
 }
 
-fn scriptfile() -> Result<PathBuf, ScriptsError> {
+/// Directory of the config directory containing plugins: scripts loaded after the scripts file.
+const PLUGINS_DIR: &str = "plugins";
+
+/// Tables of the scripts file containing functions by their names.
+const REGISTRIES: &[&str] = &["commands", "sorters", "filters", "fields"];
+
+/// Scripts file used if the user's one doesn't exist.
+pub const DEFAULT_SCRIPTS: &str = include_str!("../defaults/scripts.lua");
+
+pub fn scriptfile() -> Result<PathBuf, ScriptsError> {
     Ok(configdir()?.join("scripts.lua"))
 }
 
-pub fn open_scripts() -> Result<Scripts, ScriptsError> {
-    let lua = Lua::new_with(StdLib::ALL_SAFE, LuaOptions::new())?;
+/// Returns `*.lua` files of the plugins directory in alphabetical order.
+fn plugin_files() -> Result<Vec<PathBuf>, ScriptsError> {
+    let read_dir = match std::fs::read_dir(configdir()?.join(PLUGINS_DIR)) {
+        Ok(read_dir) => read_dir,
+        Err(io_error) if io_error.kind() == IoErrorKind::NotFound => return Ok(Vec::new()),
+        Err(io_error) => return Err(ScriptsError::IOErrorWithScriptsFile(io_error)),
+    };
 
-    let scriptfile = scriptfile()?;
-    let lua_file_content = match std::fs::read_to_string(&scriptfile) {
-        Ok(lfc) => lfc,
-        Err(io_error) if io_error.kind() == IoErrorKind::NotFound => {
-            return Err(ScriptsError::ScriptsFileWasNotFound(scriptfile));
+    let mut plugin_files = Vec::new();
+    for dir_entry in read_dir {
+        let path = dir_entry
+            .map_err(ScriptsError::IOErrorWithScriptsFile)?
+            .path();
+        if path.is_file() && path.extension().is_some_and(|extension| extension == "lua") {
+            plugin_files.push(path);
         }
+    }
+    plugin_files.sort();
+
+    Ok(plugin_files)
+}
+
+/// Executes the file. Returns `false` if it doesn't exist.
+fn exec_file(lua: &Lua, path: &Path) -> Result<bool, ScriptsError> {
+    let lua_file_content = match std::fs::read_to_string(path) {
+        Ok(lfc) => lfc,
+        Err(io_error) if io_error.kind() == IoErrorKind::NotFound => return Ok(false),
         Err(io_error) => return Err(ScriptsError::IOErrorWithScriptsFile(io_error)),
     };
 
-    lua.load(lua_file_content).exec()?;
+    // The `@` prefix makes Lua use the path in messages: `/path/to/scripts.lua:12: ...`.
+    lua.load(lua_file_content)
+        .set_name(format!("@{}", path.to_string_lossy()))
+        .exec()
+        .map_err(scripts_error)?;
+
+    Ok(true)
+}
+
+/// Returns error if the file replaced one of the registries instead of adding functions to it.
+fn check_registries(lua: &Lua, registries: &[LuaTable], file: &str) -> Result<(), ScriptsError> {
+    for (registry, table) in REGISTRIES.iter().zip(registries) {
+        match lua.globals().get::<LuaValue>(*registry)? {
+            LuaValue::Table(current) if current == *table => (),
+            _ => {
+                return Err(ScriptsError::RegistryReplaced {
+                    registry: registry.to_string(),
+                    file: file.to_string(),
+                })
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Loads scripts in the following order, so later files replace functions of earlier ones:
+/// 1. The user's scripts file or, if it doesn't exist, the default one.
+/// 2. `*.lua` files of the plugins directory in alphabetical order.
+/// 3. The scripts file of the library in the current directory, if it exists and
+///    `library_scripts` is enabled in config.
+///
+/// Registries (`commands`, `sorters`, `filters` and `fields`) are created before loading, so
+/// files add functions to them. `require` finds modules in the config directory.
+pub fn open_scripts(config: &Config) -> Result<Scripts, ScriptsError> {
+    let lua = Lua::new_with(StdLib::ALL_SAFE, LuaOptions::new())?;
+
+    let configdir = configdir()?;
+    let package = lua.globals().get::<LuaTable>("package")?;
+    package.set(
+        "path",
+        format!("{0}/?.lua;{0}/?/init.lua", configdir.to_string_lossy()),
+    )?;
+
+    lua.globals().set("popusk", PopuskModule)?;
+    let mut registries = Vec::new();
+    for registry in REGISTRIES {
+        let table = lua.create_table()?;
+        lua.globals().set(*registry, table.clone())?;
+        registries.push(table);
+    }
+
+    let scriptfile = scriptfile()?;
+    if exec_file(&lua, &scriptfile)? {
+        check_registries(&lua, &registries, &scriptfile.to_string_lossy())?;
+    } else {
+        // The `=` prefix makes Lua use the name in messages as is.
+        lua.load(DEFAULT_SCRIPTS)
+            .set_name("=default scripts.lua")
+            .exec()
+            .map_err(scripts_error)?;
+        check_registries(&lua, &registries, "default scripts.lua")?;
+    }
+    let mut files = plugin_files()?;
+    if config.library_scripts() {
+        files.push(Path::new(WORKING_DIR).join("scripts.lua"));
+    }
+    for file in files {
+        if exec_file(&lua, &file)? {
+            check_registries(&lua, &registries, &file.to_string_lossy())?;
+        }
+    }
 
-    Ok(Scripts { lua })
+    Ok(Scripts {
+        lua,
+        path: scriptfile,
+    })
 }
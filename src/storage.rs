@@ -1,7 +1,9 @@
+use crate::bibliography::Bibliography;
 use crate::entity_base::*;
 use crate::error_ext::ComError;
 use crate::id::{IDError, ID};
 use crate::progress::Progress;
+use crate::relation::Relation;
 
 use std::ffi::OsString;
 use std::io::Error as IoError;
@@ -11,16 +13,24 @@ use std::str::FromStr;
 use thiserror::Error;
 
 mod available_id_list;
+mod id_bibliography_translator;
 mod id_description_translator;
 mod id_entitybase_translator;
+mod id_path_translator;
 mod id_progress_translator;
+mod id_relations_translator;
+mod name_collection_translator;
 mod path_id_translator;
 
 use available_id_list::AvailableIDList;
+use id_bibliography_translator::{IDBibTError, IDBibliographyTranslator};
 use id_description_translator::{IDDescTError, IDDescriptionTranslator};
 use id_entitybase_translator::{IDEntitybaseTError, IDEntitybaseTranslator};
+use id_path_translator::{IDPathTError, IDPathTranslator};
 use id_progress_translator::{IDProgressTError, IDProgressTranslator};
-use path_id_translator::{PathIDTError, PathIdTranslator};
+use id_relations_translator::{IDRelTError, IDRelationsTranslator};
+use name_collection_translator::{NameCollTError, NameCollectionTranslator};
+use path_id_translator::{simplify_path_to_local, PathIDTError, PathIdTranslator};
 
 /// Name of working directory. Must contain dot in the start to be hidden.
 pub const DEFAULT_WORKING_DIR: &str = ".popusk";
@@ -41,8 +51,16 @@ pub enum StorageError {
     IDProgressT(#[from] IDProgressTError),
     #[error("path->id translator: {0}")]
     PathIDT(#[from] PathIDTError),
+    #[error("id->path translator: {0}")]
+    IDPathT(#[from] IDPathTError),
     #[error("id->description translator: {0}")]
     IDDescT(#[from] IDDescTError),
+    #[error("id->bibliography translator: {0}")]
+    IDBibT(#[from] IDBibTError),
+    #[error("id->relations translator: {0}")]
+    IDRelT(#[from] IDRelTError),
+    #[error("name->collection translator: {0}")]
+    NameCollT(#[from] NameCollTError),
     #[error("io: {0}")]
     IO(#[from] IoError),
 
@@ -69,23 +87,20 @@ trait Translator<From, To> {
 /// creating them.
 pub struct Storage {
     path_id_translator: Box<dyn Translator<PathBuf, ID>>,
+    id_path_translator: Box<dyn Translator<ID, PathBuf>>,
     id_entitybase_translator: Box<dyn Translator<ID, EntityBase>>,
     id_progress_translator: Box<dyn Translator<ID, Progress>>,
     id_description_translator: Box<dyn Translator<ID, String>>,
+    id_bibliography_translator: Box<dyn Translator<ID, Bibliography>>,
+    name_collection_translator: Box<dyn Translator<String, Vec<ID>>>,
+    id_relations_translator: Box<dyn Translator<ID, Vec<Relation>>>,
     ail: AvailableIDList,
 }
 
 impl Storage {
+    /// The same as `Self::open_with_working_dir` but working directory is set to default one.
     pub fn open() -> Result<Self, StorageError> {
-        let working_dir = PathBuf::from(DEFAULT_WORKING_DIR);
-
-        Ok(Storage {
-            path_id_translator: Box::new(PathIdTranslator::open(&working_dir)?),
-            id_entitybase_translator: Box::new(IDEntitybaseTranslator::open(&working_dir)?),
-            id_progress_translator: Box::new(IDProgressTranslator::open(&working_dir)?),
-            id_description_translator: Box::new(IDDescriptionTranslator::open(&working_dir)?),
-            ail: AvailableIDList::open(&working_dir)?,
-        })
+        Storage::open_with_working_dir(&PathBuf::from(DEFAULT_WORKING_DIR))
     }
 
     pub fn create() -> Result<Self, StorageError> {
@@ -94,9 +109,13 @@ impl Storage {
 
         Ok(Storage {
             path_id_translator: Box::new(PathIdTranslator::create(&working_dir)?),
+            id_path_translator: Box::new(IDPathTranslator::create(&working_dir)?),
             id_entitybase_translator: Box::new(IDEntitybaseTranslator::create(&working_dir)?),
             id_progress_translator: Box::new(IDProgressTranslator::create(&working_dir)?),
             id_description_translator: Box::new(IDDescriptionTranslator::create(&working_dir)?),
+            id_bibliography_translator: Box::new(IDBibliographyTranslator::create(&working_dir)?),
+            name_collection_translator: Box::new(NameCollectionTranslator::create(&working_dir)?),
+            id_relations_translator: Box::new(IDRelationsTranslator::create(&working_dir)?),
             ail: AvailableIDList::create(&working_dir)?,
         })
     }
@@ -106,23 +125,23 @@ impl Storage {
         Storage::update_with_working_dir(&PathBuf::from(DEFAULT_WORKING_DIR))
     }
 
+    /// Translators missing in libraries made by older versions are created, as by
+    /// `Self::update_with_working_dir`, so such libraries keep working without `popusk update`.
     pub fn open_with_working_dir(working_dir: &Path) -> Result<Self, StorageError> {
-        Ok(Storage {
-            path_id_translator: Box::new(PathIdTranslator::open(working_dir)?),
-            id_entitybase_translator: Box::new(IDEntitybaseTranslator::open(working_dir)?),
-            id_progress_translator: Box::new(IDProgressTranslator::open(working_dir)?),
-            id_description_translator: Box::new(IDDescriptionTranslator::open(working_dir)?),
-            ail: AvailableIDList::open(&working_dir)?,
-        })
+        Storage::update_with_working_dir(working_dir)
     }
 
     pub fn create_with_working_dir(working_dir: &Path) -> Result<Self, StorageError> {
         std::fs::create_dir(&working_dir)?;
         Ok(Storage {
             path_id_translator: Box::new(PathIdTranslator::create(working_dir)?),
+            id_path_translator: Box::new(IDPathTranslator::create(working_dir)?),
             id_entitybase_translator: Box::new(IDEntitybaseTranslator::create(working_dir)?),
             id_progress_translator: Box::new(IDProgressTranslator::create(working_dir)?),
             id_description_translator: Box::new(IDDescriptionTranslator::create(working_dir)?),
+            id_bibliography_translator: Box::new(IDBibliographyTranslator::create(working_dir)?),
+            name_collection_translator: Box::new(NameCollectionTranslator::create(working_dir)?),
+            id_relations_translator: Box::new(IDRelationsTranslator::create(working_dir)?),
             ail: AvailableIDList::create(working_dir)?,
         })
     }
@@ -136,6 +155,21 @@ impl Storage {
             Err(PathIDTError::TranslatorAlreadyExists) => PathIdTranslator::open(working_dir)?,
             Err(other_error) => return Err(other_error.into()),
         };
+        // The missing ID->path translator is built from the path->ID one.
+        let id_path_translator = match IDPathTranslator::open(working_dir) {
+            Ok(ipt) => ipt,
+            Err(IDPathTError::TranslatorDoesNotExist) => {
+                let mut translations = Vec::new();
+                for path in path_id_translator.keys()? {
+                    if let Some(id) = path_id_translator.translate(path.clone())? {
+                        translations.push((id, path));
+                    }
+                }
+
+                IDPathTranslator::build(working_dir, translations)?
+            }
+            Err(other_error) => return Err(other_error.into()),
+        };
         let id_entitybase_translator = match IDEntitybaseTranslator::create(working_dir) {
             Ok(iet) => iet,
             Err(IDEntitybaseTError::TranslatorAlreadyExists) => {
@@ -157,40 +191,87 @@ impl Storage {
             }
             Err(other_error) => return Err(other_error.into()),
         };
+        let id_bibliography_translator = match IDBibliographyTranslator::create(working_dir) {
+            Ok(ibt) => ibt,
+            Err(IDBibTError::TranslatorAlreadyExists) => {
+                IDBibliographyTranslator::open(working_dir)?
+            }
+            Err(other_error) => return Err(other_error.into()),
+        };
+        let name_collection_translator = match NameCollectionTranslator::create(working_dir) {
+            Ok(nct) => nct,
+            Err(NameCollTError::TranslatorAlreadyExists) => {
+                NameCollectionTranslator::open(working_dir)?
+            }
+            Err(other_error) => return Err(other_error.into()),
+        };
+        let id_relations_translator = match IDRelationsTranslator::create(working_dir) {
+            Ok(irt) => irt,
+            Err(IDRelTError::TranslatorAlreadyExists) => IDRelationsTranslator::open(working_dir)?,
+            Err(other_error) => return Err(other_error.into()),
+        };
 
-        Ok(Storage {
+        let storage = Storage {
             path_id_translator: Box::new(path_id_translator),
+            id_path_translator: Box::new(id_path_translator),
             id_progress_translator: Box::new(id_progress_translator),
             id_entitybase_translator: Box::new(id_entitybase_translator),
             id_description_translator: Box::new(id_description_translator),
+            id_bibliography_translator: Box::new(id_bibliography_translator),
+            name_collection_translator: Box::new(name_collection_translator),
+            id_relations_translator: Box::new(id_relations_translator),
             ail,
-        })
+        };
+
+        Ok(storage)
     }
 
     pub fn link_id_to_path(&mut self, path: PathBuf) -> Result<ID, StorageError> {
         let unique_id = self.ail.grab_id()?;
-        self.path_id_translator.add_translation(path, unique_id)?;
+        unsafe { self.link_id_to_path_raw(path, unique_id)? };
 
         Ok(unique_id)
     }
 
     pub fn unlink_id_from_path(&mut self, path: PathBuf) -> Result<ID, StorageError> {
-        let id = self.path_id_translator.del_translation(path)?;
+        let id = unsafe { self.unlink_id_from_path_raw(path)? };
         self.ail.release_id(id)?;
 
         Ok(id)
     }
 
+    /// Both path->ID and ID->path translations are added.
     pub unsafe fn link_id_to_path_raw(
         &mut self,
         path: PathBuf,
         id: ID,
     ) -> Result<(), StorageError> {
-        self.path_id_translator.add_translation(path, id)
+        let local_path = simplify_path_to_local(&path)?;
+
+        self.path_id_translator.add_translation(path.clone(), id)?;
+        if let Err(err) = self.id_path_translator.add_translation(id, local_path) {
+            // The translators mustn't drift apart, so the path->ID translation is rolled back.
+            self.path_id_translator.del_translation(path)?;
+            return Err(err);
+        }
+
+        Ok(())
     }
 
+    /// Both path->ID and ID->path translations are deleted. A missing ID->path translation isn't
+    /// an error, cause there's nothing to delete.
     pub unsafe fn unlink_id_from_path_raw(&mut self, path: PathBuf) -> Result<ID, StorageError> {
-        self.path_id_translator.del_translation(path)
+        let id = self.path_id_translator.del_translation(path.clone())?;
+        match self.id_path_translator.del_translation(id) {
+            Ok(_) | Err(StorageError::IDPathT(IDPathTError::PathDoesNotExist { .. })) => (),
+            Err(err) => {
+                // The translators mustn't drift apart, so the path->ID translation is restored.
+                self.path_id_translator.add_translation(path, id)?;
+                return Err(err);
+            }
+        }
+
+        Ok(id)
     }
 
     pub fn link_progress_to_id(&mut self, id: ID, progress: Progress) -> Result<(), StorageError> {
@@ -236,6 +317,75 @@ impl Storage {
         self.id_description_translator.del_translation(id)
     }
 
+    pub fn update_description(
+        &mut self,
+        id: ID,
+        new_description: String,
+    ) -> Result<String, StorageError> {
+        self.id_description_translator
+            .update_translation(id, new_description)
+    }
+
+    pub fn link_bibliography_to_id(
+        &mut self,
+        id: ID,
+        bibliography: Bibliography,
+    ) -> Result<(), StorageError> {
+        self.id_bibliography_translator
+            .add_translation(id, bibliography)
+    }
+
+    pub fn unlink_bibliography_from_id(&mut self, id: ID) -> Result<Bibliography, StorageError> {
+        self.id_bibliography_translator.del_translation(id)
+    }
+
+    pub fn update_bibliography(
+        &mut self,
+        id: ID,
+        new_bibliography: Bibliography,
+    ) -> Result<Bibliography, StorageError> {
+        self.id_bibliography_translator
+            .update_translation(id, new_bibliography)
+    }
+
+    pub fn link_relations_to_id(
+        &mut self,
+        id: ID,
+        relations: Vec<Relation>,
+    ) -> Result<(), StorageError> {
+        self.id_relations_translator.add_translation(id, relations)
+    }
+
+    pub fn unlink_relations_from_id(&mut self, id: ID) -> Result<Vec<Relation>, StorageError> {
+        self.id_relations_translator.del_translation(id)
+    }
+
+    pub fn update_relations(
+        &mut self,
+        id: ID,
+        new_relations: Vec<Relation>,
+    ) -> Result<Vec<Relation>, StorageError> {
+        self.id_relations_translator
+            .update_translation(id, new_relations)
+    }
+
+    pub fn create_collection(&mut self, name: String, ids: Vec<ID>) -> Result<(), StorageError> {
+        self.name_collection_translator.add_translation(name, ids)
+    }
+
+    pub fn delete_collection(&mut self, name: String) -> Result<Vec<ID>, StorageError> {
+        self.name_collection_translator.del_translation(name)
+    }
+
+    pub fn update_collection(
+        &mut self,
+        name: String,
+        new_ids: Vec<ID>,
+    ) -> Result<Vec<ID>, StorageError> {
+        self.name_collection_translator
+            .update_translation(name, new_ids)
+    }
+
     pub fn update_entitybase(
         &mut self,
         id: ID,
@@ -249,6 +399,10 @@ impl Storage {
         self.path_id_translator.translate(path)
     }
 
+    pub fn get_path(&self, id: ID) -> Result<Option<PathBuf>, StorageError> {
+        self.id_path_translator.translate(id)
+    }
+
     pub fn get_progress(&self, id: ID) -> Result<Option<Progress>, StorageError> {
         self.id_progress_translator.translate(id)
     }
@@ -261,7 +415,32 @@ impl Storage {
         self.id_description_translator.translate(id)
     }
 
+    pub fn get_bibliography(&self, id: ID) -> Result<Option<Bibliography>, StorageError> {
+        self.id_bibliography_translator.translate(id)
+    }
+
+    pub fn get_relations(&self, id: ID) -> Result<Option<Vec<Relation>>, StorageError> {
+        self.id_relations_translator.translate(id)
+    }
+
+    /// Returns IDs having relations.
+    pub fn keys_relations(&self) -> Result<Vec<ID>, StorageError> {
+        self.id_relations_translator.keys()
+    }
+
+    pub fn get_collection(&self, name: String) -> Result<Option<Vec<ID>>, StorageError> {
+        self.name_collection_translator.translate(name)
+    }
+
+    pub fn keys_collection(&self) -> Result<Vec<String>, StorageError> {
+        self.name_collection_translator.keys()
+    }
+
     pub fn keys_path(&self) -> Result<Vec<PathBuf>, StorageError> {
         self.path_id_translator.keys()
     }
+
+    pub fn keys_id(&self) -> Result<Vec<ID>, StorageError> {
+        self.id_entitybase_translator.keys()
+    }
 }
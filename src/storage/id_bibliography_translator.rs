@@ -0,0 +1,211 @@
+use super::{filename_from_id, id_from_filename, StorageError, Translator};
+
+use crate::bibliography::Bibliography;
+use crate::error_ext::{ComError, CommonizeResultExt};
+use crate::id::ID;
+
+use std::fs::{File, OpenOptions};
+use std::io::{Error as IoError, ErrorKind as IoErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+
+use bincode::{
+    deserialize as bincode_deserialize, serialize as bincode_serialize, Error as BincodeError,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum IDBibTError {
+    #[error("couldn't make a translator because it already exists")]
+    TranslatorAlreadyExists,
+    #[error("couldn't open a translator because it doesn't exist")]
+    TranslatorDoesNotExist,
+    #[error("directory doesn't exist: {0}")]
+    DirectoryDoesNotExist(PathBuf),
+    #[error("couldn't find the file: {0}")]
+    FileDoesNotExist(PathBuf),
+    #[error("bibliography doesn't exist for ID {id}")]
+    BibDoesNotExist { id: ID },
+    #[error("bibliography already exists for ID {id}")]
+    BibAlreadyExists { id: ID },
+    #[error("serialization/deserialization error: {0}")]
+    SerDeserError(#[from] BincodeError),
+    #[error("an I/O error occured: {0}")]
+    IO(#[from] IoError),
+
+    #[error("{0}")]
+    Other(#[from] ComError),
+}
+
+const ID_BIB_TRANSLATIONS_DIR: &str = "idbib_t";
+
+fn translations_dir(working_dir: &Path) -> PathBuf {
+    working_dir.join(ID_BIB_TRANSLATIONS_DIR)
+}
+
+pub struct IDBibliographyTranslator {
+    translations_dir: PathBuf,
+}
+
+impl IDBibliographyTranslator {
+    pub fn open(working_dir: &Path) -> Result<Self, IDBibTError> {
+        let translations_dir = translations_dir(working_dir);
+
+        if !translations_dir.exists() {
+            return Err(IDBibTError::TranslatorDoesNotExist);
+        }
+
+        Ok(IDBibliographyTranslator { translations_dir })
+    }
+
+    pub fn create(working_dir: &Path) -> Result<Self, IDBibTError> {
+        let translations_dir = translations_dir(working_dir);
+
+        match std::fs::create_dir(&translations_dir) {
+            Err(io_error) if io_error.kind() == IoErrorKind::AlreadyExists => {
+                return Err(IDBibTError::TranslatorAlreadyExists)
+            }
+            Err(io_error) => return Err(io_error.into()),
+            Ok(_) => (),
+        }
+
+        Ok(IDBibliographyTranslator { translations_dir })
+    }
+
+    fn translate_inner(&self, key: ID) -> Result<Option<Bibliography>, IDBibTError> {
+        let bib_filename = filename_from_id(key);
+        let bib_file_path = self.translations_dir.join(bib_filename);
+
+        let mut file = match File::open(&bib_file_path) {
+            Ok(file) => file,
+            Err(io_error) if io_error.kind() == IoErrorKind::NotFound => {
+                return Ok(None);
+            }
+            Err(io_error) => return Err(io_error.into()),
+        };
+        let mut serialized_bib: Vec<u8> = Vec::new();
+        file.read_to_end(&mut serialized_bib)?;
+        let bibliography: Bibliography = bincode_deserialize(&serialized_bib)?;
+
+        Ok(Some(bibliography))
+    }
+
+    fn keys_inner(&self) -> Result<Vec<ID>, IDBibTError> {
+        let mut translations = Vec::new();
+
+        for entry in self.translations_dir.read_dir()? {
+            let entry = entry?;
+
+            let id = id_from_filename(entry.file_name()).commonize()?;
+            translations.push(id);
+        }
+
+        Ok(translations)
+    }
+
+    fn add_translation_inner(&mut self, id: ID, bib: Bibliography) -> Result<(), IDBibTError> {
+        let bib_filename = filename_from_id(id);
+        let bib_file_path = self.translations_dir.join(bib_filename);
+
+        let serialized_bib = bincode_serialize(&bib)?;
+        let mut file = match File::create_new(&bib_file_path) {
+            Ok(file) => file,
+            Err(io_error) if io_error.kind() == IoErrorKind::AlreadyExists => {
+                return Err(IDBibTError::BibAlreadyExists { id })
+            }
+
+            Err(io_error) => return Err(io_error.into()),
+        };
+        file.write_all(&serialized_bib)?;
+
+        Ok(())
+    }
+
+    fn del_translation_inner(&mut self, id: ID) -> Result<Bibliography, IDBibTError> {
+        let bib_filename = filename_from_id(id);
+        let bib_file_path = self.translations_dir.join(bib_filename);
+
+        let mut file = match File::open(&bib_file_path) {
+            Ok(file) => file,
+            Err(io_error) if io_error.kind() == IoErrorKind::NotFound => {
+                return Err(IDBibTError::BibDoesNotExist { id });
+            }
+            Err(io_error) => {
+                return Err(io_error.into());
+            }
+        };
+        let mut serialized_bib: Vec<u8> = Vec::new();
+        file.read_to_end(&mut serialized_bib)?;
+        let bibliography: Bibliography = bincode_deserialize(&serialized_bib)?;
+
+        std::fs::remove_file(&bib_file_path)?;
+
+        Ok(bibliography)
+    }
+
+    fn update_translation_inner(
+        &mut self,
+        id: ID,
+        new_bib: Bibliography,
+    ) -> Result<Bibliography, IDBibTError> {
+        let bib_filename = filename_from_id(id);
+        let bib_file_path = self.translations_dir.join(bib_filename);
+
+        let mut read_file = match File::open(&bib_file_path) {
+            Ok(file) => file,
+            Err(io_error) if io_error.kind() == IoErrorKind::NotFound => {
+                return Err(IDBibTError::BibDoesNotExist { id });
+            }
+            Err(io_error) => return Err(io_error.into()),
+        };
+        let mut serialized_old_bib: Vec<u8> = Vec::new();
+        read_file.read_to_end(&mut serialized_old_bib)?;
+        let old_bib = bincode_deserialize(&serialized_old_bib)?;
+        drop(read_file);
+
+        // Without catching `io_error` whose kind is `NotFount`. It is because of we already know
+        // that file `bib_file_path` points on exists.
+        let mut write_file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .open(&bib_file_path)?;
+        write_file.write_all(&bincode_serialize(&new_bib)?)?;
+
+        Ok(old_bib)
+    }
+}
+
+impl Translator<ID, Bibliography> for IDBibliographyTranslator {
+    fn translate(&self, key: ID) -> Result<Option<Bibliography>, StorageError> {
+        Ok(self.translate_inner(key)?)
+    }
+
+    fn keys(&self) -> Result<Vec<ID>, StorageError> {
+        Ok(self.keys_inner()?)
+    }
+
+    fn add_translation(&mut self, key: ID, value: Bibliography) -> Result<(), StorageError> {
+        Ok(self.add_translation_inner(key, value)?)
+    }
+
+    fn del_translation(&mut self, key: ID) -> Result<Bibliography, StorageError> {
+        Ok(self.del_translation_inner(key)?)
+    }
+
+    fn update_translation(
+        &mut self,
+        key: ID,
+        new_value: Bibliography,
+    ) -> Result<Bibliography, StorageError> {
+        Ok(self.update_translation_inner(key, new_value)?)
+    }
+
+    fn load(&mut self) -> Result<(), StorageError> {
+        // All `<Self as Translator>` functions works immediatly with file system.
+        Ok(())
+    }
+
+    fn store(&mut self) -> Result<(), StorageError> {
+        // All `<Self as Translator>` functions works immediatly with file system.
+        Ok(())
+    }
+}
@@ -0,0 +1,241 @@
+use super::{filename_from_id, id_from_filename, StorageError, Translator};
+
+use crate::error_ext::{ComError, CommonizeResultExt};
+use crate::id::ID;
+
+use std::fs::{File, OpenOptions};
+use std::io::{Error as IoError, ErrorKind as IoErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+
+use bincode::{
+    deserialize as bincode_deserialize, serialize as bincode_serialize, Error as BincodeError,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum IDPathTError {
+    #[error("couldn't make a translator because it already exists")]
+    TranslatorAlreadyExists,
+    #[error("couldn't open a translator because it doesn't exist")]
+    TranslatorDoesNotExist,
+    #[error("directory doesn't exist: {0}")]
+    DirectoryDoesNotExist(PathBuf),
+    #[error("couldn't find the file: {0}")]
+    FileDoesNotExist(PathBuf),
+    #[error("path doesn't exist for ID {id}")]
+    PathDoesNotExist { id: ID },
+    #[error("path already exists for ID {id}")]
+    PathAlreadyExists { id: ID },
+    #[error("serialization/deserialization error: {0}")]
+    SerDeserError(#[from] BincodeError),
+    #[error("an I/O error occured: {0}")]
+    IO(#[from] IoError),
+
+    #[error("{0}")]
+    Other(#[from] ComError),
+}
+
+const ID_PATH_TRANSLATIONS_DIR: &str = "idpath_t";
+
+fn translations_dir(working_dir: &Path) -> PathBuf {
+    working_dir.join(ID_PATH_TRANSLATIONS_DIR)
+}
+
+/// Reverse of `PathIdTranslator`. It's kept by `Storage` consistent with `PathIdTranslator`, paths
+/// are stored in the simplified local form.
+pub struct IDPathTranslator {
+    translations_dir: PathBuf,
+}
+
+impl IDPathTranslator {
+    pub fn open(working_dir: &Path) -> Result<Self, IDPathTError> {
+        let translations_dir = translations_dir(working_dir);
+
+        if !translations_dir.exists() {
+            return Err(IDPathTError::TranslatorDoesNotExist);
+        }
+
+        Ok(IDPathTranslator { translations_dir })
+    }
+
+    pub fn create(working_dir: &Path) -> Result<Self, IDPathTError> {
+        let translations_dir = translations_dir(working_dir);
+
+        match std::fs::create_dir(&translations_dir) {
+            Err(io_error) if io_error.kind() == IoErrorKind::AlreadyExists => {
+                return Err(IDPathTError::TranslatorAlreadyExists)
+            }
+            Err(io_error) => return Err(io_error.into()),
+            Ok(_) => (),
+        }
+
+        Ok(IDPathTranslator { translations_dir })
+    }
+
+    /// Creates the translator filled with the given translations. They're written to a temporary
+    /// directory renamed only after all of them are written, so an interrupted build leaves no
+    /// translator and it's built again on the next open.
+    pub fn build(
+        working_dir: &Path,
+        translations: Vec<(ID, PathBuf)>,
+    ) -> Result<Self, IDPathTError> {
+        let translations_dir = translations_dir(working_dir);
+        if translations_dir.exists() {
+            return Err(IDPathTError::TranslatorAlreadyExists);
+        }
+
+        // Leftovers of an interrupted build are dropped.
+        let building_dir = working_dir.join(format!("{}.building", ID_PATH_TRANSLATIONS_DIR));
+        match std::fs::remove_dir_all(&building_dir) {
+            Err(io_error) if io_error.kind() != IoErrorKind::NotFound => {
+                return Err(io_error.into())
+            }
+            _ => (),
+        }
+        std::fs::create_dir(&building_dir)?;
+
+        let mut building_translator = IDPathTranslator {
+            translations_dir: building_dir.clone(),
+        };
+        for (id, path) in translations {
+            building_translator.add_translation_inner(id, path)?;
+        }
+        std::fs::rename(&building_dir, &translations_dir)?;
+
+        Ok(IDPathTranslator { translations_dir })
+    }
+
+    fn translate_inner(&self, key: ID) -> Result<Option<PathBuf>, IDPathTError> {
+        let path_filename = filename_from_id(key);
+        let path_file_path = self.translations_dir.join(path_filename);
+
+        let mut file = match File::open(&path_file_path) {
+            Ok(file) => file,
+            Err(io_error) if io_error.kind() == IoErrorKind::NotFound => {
+                return Ok(None);
+            }
+            Err(io_error) => return Err(io_error.into()),
+        };
+        let mut serialized_path: Vec<u8> = Vec::new();
+        file.read_to_end(&mut serialized_path)?;
+        let path: PathBuf = bincode_deserialize(&serialized_path)?;
+
+        Ok(Some(path))
+    }
+
+    fn keys_inner(&self) -> Result<Vec<ID>, IDPathTError> {
+        let mut translations = Vec::new();
+
+        for entry in self.translations_dir.read_dir()? {
+            let entry = entry?;
+
+            let id = id_from_filename(entry.file_name()).commonize()?;
+            translations.push(id);
+        }
+
+        Ok(translations)
+    }
+
+    fn add_translation_inner(&mut self, id: ID, path: PathBuf) -> Result<(), IDPathTError> {
+        let path_filename = filename_from_id(id);
+        let path_file_path = self.translations_dir.join(path_filename);
+
+        let serialized_path = bincode_serialize(&path)?;
+        let mut file = match File::create_new(&path_file_path) {
+            Ok(file) => file,
+            Err(io_error) if io_error.kind() == IoErrorKind::AlreadyExists => {
+                return Err(IDPathTError::PathAlreadyExists { id })
+            }
+
+            Err(io_error) => return Err(io_error.into()),
+        };
+        file.write_all(&serialized_path)?;
+
+        Ok(())
+    }
+
+    fn del_translation_inner(&mut self, id: ID) -> Result<PathBuf, IDPathTError> {
+        let path_filename = filename_from_id(id);
+        let path_file_path = self.translations_dir.join(path_filename);
+
+        let mut file = match File::open(&path_file_path) {
+            Ok(file) => file,
+            Err(io_error) if io_error.kind() == IoErrorKind::NotFound => {
+                return Err(IDPathTError::PathDoesNotExist { id });
+            }
+            Err(io_error) => {
+                return Err(io_error.into());
+            }
+        };
+        let mut serialized_path: Vec<u8> = Vec::new();
+        file.read_to_end(&mut serialized_path)?;
+        let path: PathBuf = bincode_deserialize(&serialized_path)?;
+
+        std::fs::remove_file(&path_file_path)?;
+
+        Ok(path)
+    }
+
+    fn update_translation_inner(
+        &mut self,
+        id: ID,
+        new_path: PathBuf,
+    ) -> Result<PathBuf, IDPathTError> {
+        let path_filename = filename_from_id(id);
+        let path_file_path = self.translations_dir.join(path_filename);
+
+        let mut read_file = match File::open(&path_file_path) {
+            Ok(file) => file,
+            Err(io_error) if io_error.kind() == IoErrorKind::NotFound => {
+                return Err(IDPathTError::PathDoesNotExist { id });
+            }
+            Err(io_error) => return Err(io_error.into()),
+        };
+        let mut serialized_old_path: Vec<u8> = Vec::new();
+        read_file.read_to_end(&mut serialized_old_path)?;
+        let old_path = bincode_deserialize(&serialized_old_path)?;
+        drop(read_file);
+
+        // Without catching `io_error` whose kind is `NotFount`. It is because of we already know
+        // that file `path_file_path` points on exists.
+        let mut write_file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .open(&path_file_path)?;
+        write_file.write_all(&bincode_serialize(&new_path)?)?;
+
+        Ok(old_path)
+    }
+}
+
+impl Translator<ID, PathBuf> for IDPathTranslator {
+    fn translate(&self, key: ID) -> Result<Option<PathBuf>, StorageError> {
+        Ok(self.translate_inner(key)?)
+    }
+
+    fn keys(&self) -> Result<Vec<ID>, StorageError> {
+        Ok(self.keys_inner()?)
+    }
+
+    fn add_translation(&mut self, key: ID, value: PathBuf) -> Result<(), StorageError> {
+        Ok(self.add_translation_inner(key, value)?)
+    }
+
+    fn del_translation(&mut self, key: ID) -> Result<PathBuf, StorageError> {
+        Ok(self.del_translation_inner(key)?)
+    }
+
+    fn update_translation(&mut self, key: ID, new_value: PathBuf) -> Result<PathBuf, StorageError> {
+        Ok(self.update_translation_inner(key, new_value)?)
+    }
+
+    fn load(&mut self) -> Result<(), StorageError> {
+        // All `<Self as Translator>` functions works immediatly with file system.
+        Ok(())
+    }
+
+    fn store(&mut self) -> Result<(), StorageError> {
+        // All `<Self as Translator>` functions works immediatly with file system.
+        Ok(())
+    }
+}
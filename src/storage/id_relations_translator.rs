@@ -0,0 +1,215 @@
+use super::{filename_from_id, id_from_filename, StorageError, Translator};
+
+use crate::error_ext::{ComError, CommonizeResultExt};
+use crate::id::ID;
+use crate::relation::Relation;
+
+use std::fs::{File, OpenOptions};
+use std::io::{Error as IoError, ErrorKind as IoErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+
+use bincode::{
+    deserialize as bincode_deserialize, serialize as bincode_serialize, Error as BincodeError,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum IDRelTError {
+    #[error("couldn't make a translator because it already exists")]
+    TranslatorAlreadyExists,
+    #[error("couldn't open a translator because it doesn't exist")]
+    TranslatorDoesNotExist,
+    #[error("directory doesn't exist: {0}")]
+    DirectoryDoesNotExist(PathBuf),
+    #[error("couldn't find the file: {0}")]
+    FileDoesNotExist(PathBuf),
+    #[error("relations don't exist for ID {id}")]
+    RelDoesNotExist { id: ID },
+    #[error("relations already exist for ID {id}")]
+    RelAlreadyExists { id: ID },
+    #[error("serialization/deserialization error: {0}")]
+    SerDeserError(#[from] BincodeError),
+    #[error("an I/O error occured: {0}")]
+    IO(#[from] IoError),
+
+    #[error("{0}")]
+    Other(#[from] ComError),
+}
+
+const ID_REL_TRANSLATIONS_DIR: &str = "idrel_t";
+
+fn translations_dir(working_dir: &Path) -> PathBuf {
+    working_dir.join(ID_REL_TRANSLATIONS_DIR)
+}
+
+pub struct IDRelationsTranslator {
+    translations_dir: PathBuf,
+}
+
+impl IDRelationsTranslator {
+    pub fn open(working_dir: &Path) -> Result<Self, IDRelTError> {
+        let translations_dir = translations_dir(working_dir);
+
+        if !translations_dir.exists() {
+            return Err(IDRelTError::TranslatorDoesNotExist);
+        }
+
+        Ok(IDRelationsTranslator { translations_dir })
+    }
+
+    pub fn create(working_dir: &Path) -> Result<Self, IDRelTError> {
+        let translations_dir = translations_dir(working_dir);
+
+        match std::fs::create_dir(&translations_dir) {
+            Err(io_error) if io_error.kind() == IoErrorKind::AlreadyExists => {
+                return Err(IDRelTError::TranslatorAlreadyExists)
+            }
+            Err(io_error) => return Err(io_error.into()),
+            Ok(_) => (),
+        }
+
+        Ok(IDRelationsTranslator { translations_dir })
+    }
+
+    fn translate_inner(&self, key: ID) -> Result<Option<Vec<Relation>>, IDRelTError> {
+        let rel_filename = filename_from_id(key);
+        let rel_file_path = self.translations_dir.join(rel_filename);
+
+        let mut file = match File::open(&rel_file_path) {
+            Ok(file) => file,
+            Err(io_error) if io_error.kind() == IoErrorKind::NotFound => {
+                return Ok(None);
+            }
+            Err(io_error) => return Err(io_error.into()),
+        };
+        let mut serialized_relations: Vec<u8> = Vec::new();
+        file.read_to_end(&mut serialized_relations)?;
+        let relations: Vec<Relation> = bincode_deserialize(&serialized_relations)?;
+
+        Ok(Some(relations))
+    }
+
+    fn keys_inner(&self) -> Result<Vec<ID>, IDRelTError> {
+        let mut translations = Vec::new();
+
+        for entry in self.translations_dir.read_dir()? {
+            let entry = entry?;
+
+            let id = id_from_filename(entry.file_name()).commonize()?;
+            translations.push(id);
+        }
+
+        Ok(translations)
+    }
+
+    fn add_translation_inner(
+        &mut self,
+        id: ID,
+        relations: Vec<Relation>,
+    ) -> Result<(), IDRelTError> {
+        let rel_filename = filename_from_id(id);
+        let rel_file_path = self.translations_dir.join(rel_filename);
+
+        let serialized_relations = bincode_serialize(&relations)?;
+        let mut file = match File::create_new(&rel_file_path) {
+            Ok(file) => file,
+            Err(io_error) if io_error.kind() == IoErrorKind::AlreadyExists => {
+                return Err(IDRelTError::RelAlreadyExists { id })
+            }
+
+            Err(io_error) => return Err(io_error.into()),
+        };
+        file.write_all(&serialized_relations)?;
+
+        Ok(())
+    }
+
+    fn del_translation_inner(&mut self, id: ID) -> Result<Vec<Relation>, IDRelTError> {
+        let rel_filename = filename_from_id(id);
+        let rel_file_path = self.translations_dir.join(rel_filename);
+
+        let mut file = match File::open(&rel_file_path) {
+            Ok(file) => file,
+            Err(io_error) if io_error.kind() == IoErrorKind::NotFound => {
+                return Err(IDRelTError::RelDoesNotExist { id });
+            }
+            Err(io_error) => {
+                return Err(io_error.into());
+            }
+        };
+        let mut serialized_relations: Vec<u8> = Vec::new();
+        file.read_to_end(&mut serialized_relations)?;
+        let relations: Vec<Relation> = bincode_deserialize(&serialized_relations)?;
+
+        std::fs::remove_file(&rel_file_path)?;
+
+        Ok(relations)
+    }
+
+    fn update_translation_inner(
+        &mut self,
+        id: ID,
+        new_relations: Vec<Relation>,
+    ) -> Result<Vec<Relation>, IDRelTError> {
+        let rel_filename = filename_from_id(id);
+        let rel_file_path = self.translations_dir.join(rel_filename);
+
+        let mut read_file = match File::open(&rel_file_path) {
+            Ok(file) => file,
+            Err(io_error) if io_error.kind() == IoErrorKind::NotFound => {
+                return Err(IDRelTError::RelDoesNotExist { id });
+            }
+            Err(io_error) => return Err(io_error.into()),
+        };
+        let mut serialized_old_relations: Vec<u8> = Vec::new();
+        read_file.read_to_end(&mut serialized_old_relations)?;
+        let old_relations = bincode_deserialize(&serialized_old_relations)?;
+        drop(read_file);
+
+        // Without catching `io_error` whose kind is `NotFount`. It is because of we already know
+        // that file `rel_file_path` points on exists.
+        let mut write_file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .open(&rel_file_path)?;
+        write_file.write_all(&bincode_serialize(&new_relations)?)?;
+
+        Ok(old_relations)
+    }
+}
+
+impl Translator<ID, Vec<Relation>> for IDRelationsTranslator {
+    fn translate(&self, key: ID) -> Result<Option<Vec<Relation>>, StorageError> {
+        Ok(self.translate_inner(key)?)
+    }
+
+    fn keys(&self) -> Result<Vec<ID>, StorageError> {
+        Ok(self.keys_inner()?)
+    }
+
+    fn add_translation(&mut self, key: ID, value: Vec<Relation>) -> Result<(), StorageError> {
+        Ok(self.add_translation_inner(key, value)?)
+    }
+
+    fn del_translation(&mut self, key: ID) -> Result<Vec<Relation>, StorageError> {
+        Ok(self.del_translation_inner(key)?)
+    }
+
+    fn update_translation(
+        &mut self,
+        key: ID,
+        new_value: Vec<Relation>,
+    ) -> Result<Vec<Relation>, StorageError> {
+        Ok(self.update_translation_inner(key, new_value)?)
+    }
+
+    fn load(&mut self) -> Result<(), StorageError> {
+        // All `<Self as Translator>` functions works immediatly with file system.
+        Ok(())
+    }
+
+    fn store(&mut self) -> Result<(), StorageError> {
+        // All `<Self as Translator>` functions works immediatly with file system.
+        Ok(())
+    }
+}
@@ -0,0 +1,203 @@
+use super::{StorageError, Translator};
+
+use crate::error_ext::ComError;
+use crate::id::ID;
+
+use std::fs::{File, OpenOptions};
+use std::io::{Error as IoError, ErrorKind as IoErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+
+use bincode::{
+    deserialize as bincode_deserialize, serialize as bincode_serialize, Error as BincodeError,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum NameCollTError {
+    #[error("couldn't make a translator because it already exists")]
+    TranslatorAlreadyExists,
+    #[error("couldn't open a translator because it doesn't exist")]
+    TranslatorDoesNotExist,
+    #[error("directory doesn't exist: {0}")]
+    DirectoryDoesNotExist(PathBuf),
+    #[error("couldn't find the file: {0}")]
+    FileDoesNotExist(PathBuf),
+    #[error("name of collection can't be used as file name: '{name}'")]
+    InvalidName { name: String },
+    #[error("collection '{name}' doesn't exist")]
+    CollDoesNotExist { name: String },
+    #[error("collection '{name}' already exists")]
+    CollAlreadyExists { name: String },
+    #[error("serialization/deserialization error: {0}")]
+    SerDeserError(#[from] BincodeError),
+    #[error("an I/O error occured: {0}")]
+    IO(#[from] IoError),
+
+    #[error("{0}")]
+    Other(#[from] ComError),
+}
+
+const NAME_COLL_TRANSLATIONS_DIR: &str = "namecoll_t";
+
+fn translations_dir(working_dir: &Path) -> PathBuf {
+    working_dir.join(NAME_COLL_TRANSLATIONS_DIR)
+}
+
+/// Names of collections are used as file names, so they can't contain path separators and start
+/// with a dot.
+fn check_name(name: &str) -> Result<(), NameCollTError> {
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\0']) {
+        return Err(NameCollTError::InvalidName {
+            name: name.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Keeps collections as name -> IDs of the members. Order of the IDs is the order of the
+/// collection.
+pub struct NameCollectionTranslator {
+    translations_dir: PathBuf,
+}
+
+impl NameCollectionTranslator {
+    pub fn open(working_dir: &Path) -> Result<Self, NameCollTError> {
+        let translations_dir = translations_dir(working_dir);
+
+        if !translations_dir.exists() {
+            return Err(NameCollTError::TranslatorDoesNotExist);
+        }
+
+        Ok(NameCollectionTranslator { translations_dir })
+    }
+
+    pub fn create(working_dir: &Path) -> Result<Self, NameCollTError> {
+        let translations_dir = translations_dir(working_dir);
+
+        match std::fs::create_dir(&translations_dir) {
+            Err(io_error) if io_error.kind() == IoErrorKind::AlreadyExists => {
+                return Err(NameCollTError::TranslatorAlreadyExists)
+            }
+            Err(io_error) => return Err(io_error.into()),
+            Ok(_) => (),
+        }
+
+        Ok(NameCollectionTranslator { translations_dir })
+    }
+
+    fn translate_inner(&self, name: String) -> Result<Option<Vec<ID>>, NameCollTError> {
+        check_name(&name)?;
+        let coll_file_path = self.translations_dir.join(&name);
+
+        let mut file = match File::open(&coll_file_path) {
+            Ok(file) => file,
+            Err(io_error) if io_error.kind() == IoErrorKind::NotFound => {
+                return Ok(None);
+            }
+            Err(io_error) => return Err(io_error.into()),
+        };
+        let mut serialized_coll: Vec<u8> = Vec::new();
+        file.read_to_end(&mut serialized_coll)?;
+        let ids: Vec<ID> = bincode_deserialize(&serialized_coll)?;
+
+        Ok(Some(ids))
+    }
+
+    fn keys_inner(&self) -> Result<Vec<String>, NameCollTError> {
+        let mut translations = Vec::new();
+
+        for entry in self.translations_dir.read_dir()? {
+            let entry = entry?;
+
+            translations.push(entry.file_name().to_string_lossy().to_string());
+        }
+
+        Ok(translations)
+    }
+
+    fn add_translation_inner(&mut self, name: String, ids: Vec<ID>) -> Result<(), NameCollTError> {
+        check_name(&name)?;
+        let coll_file_path = self.translations_dir.join(&name);
+
+        let serialized_coll = bincode_serialize(&ids)?;
+        let mut file = match File::create_new(&coll_file_path) {
+            Ok(file) => file,
+            Err(io_error) if io_error.kind() == IoErrorKind::AlreadyExists => {
+                return Err(NameCollTError::CollAlreadyExists { name })
+            }
+
+            Err(io_error) => return Err(io_error.into()),
+        };
+        file.write_all(&serialized_coll)?;
+
+        Ok(())
+    }
+
+    fn del_translation_inner(&mut self, name: String) -> Result<Vec<ID>, NameCollTError> {
+        let ids = match self.translate_inner(name.clone())? {
+            Some(ids) => ids,
+            None => return Err(NameCollTError::CollDoesNotExist { name }),
+        };
+
+        std::fs::remove_file(self.translations_dir.join(&name))?;
+
+        Ok(ids)
+    }
+
+    fn update_translation_inner(
+        &mut self,
+        name: String,
+        new_ids: Vec<ID>,
+    ) -> Result<Vec<ID>, NameCollTError> {
+        let old_ids = match self.translate_inner(name.clone())? {
+            Some(ids) => ids,
+            None => return Err(NameCollTError::CollDoesNotExist { name }),
+        };
+
+        // The file exists because it's just been read.
+        let mut write_file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .open(self.translations_dir.join(&name))?;
+        write_file.write_all(&bincode_serialize(&new_ids)?)?;
+
+        Ok(old_ids)
+    }
+}
+
+impl Translator<String, Vec<ID>> for NameCollectionTranslator {
+    fn translate(&self, key: String) -> Result<Option<Vec<ID>>, StorageError> {
+        Ok(self.translate_inner(key)?)
+    }
+
+    fn keys(&self) -> Result<Vec<String>, StorageError> {
+        Ok(self.keys_inner()?)
+    }
+
+    fn add_translation(&mut self, key: String, value: Vec<ID>) -> Result<(), StorageError> {
+        Ok(self.add_translation_inner(key, value)?)
+    }
+
+    fn del_translation(&mut self, key: String) -> Result<Vec<ID>, StorageError> {
+        Ok(self.del_translation_inner(key)?)
+    }
+
+    fn update_translation(
+        &mut self,
+        key: String,
+        new_value: Vec<ID>,
+    ) -> Result<Vec<ID>, StorageError> {
+        Ok(self.update_translation_inner(key, new_value)?)
+    }
+
+    fn load(&mut self) -> Result<(), StorageError> {
+        // All `<Self as Translator>` functions works immediatly with file system.
+        Ok(())
+    }
+
+    fn store(&mut self) -> Result<(), StorageError> {
+        // All `<Self as Translator>` functions works immediatly with file system.
+        Ok(())
+    }
+}
@@ -50,7 +50,7 @@ pub enum PathIDTError {
 /// ## Examples
 /// 1. `./dir/../dir2/file` => `dir2/file`
 /// 2. `a/./b/../c/` => `a/c`
-fn simplify_path_to_local(path: &Path) -> Result<PathBuf, PathIDTError> {
+pub(super) fn simplify_path_to_local(path: &Path) -> Result<PathBuf, PathIDTError> {
     let mut new_components: Vec<Component> = Vec::new();
 
     // '... a/./c/ ...' => '... a/c ...'.
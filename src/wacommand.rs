@@ -1,7 +1,12 @@
 use crate::app::AppError;
+use crate::config::{configfile, read_config, ConfigError, DEFAULT_CONFIG};
 use crate::core_commands::CoreError;
+use crate::lua_library::LuaLibrary;
+use crate::parse_cli::exit_unknown_subcommand;
+use crate::scripts::{open_scripts, scriptfile, Context, ScriptsError, DEFAULT_SCRIPTS};
 
 use std::io::Error as IoError;
+use std::path::PathBuf;
 
 use bincode::Error as BincodeError;
 use thiserror::Error;
@@ -16,6 +21,14 @@ pub enum WAExexutionError {
     IO(#[from] IoError),
     #[error("serialization/deserialization error: {0}")]
     SerDeser(#[from] BincodeError),
+    #[error("config: {0}")]
+    ConfigError(#[from] ConfigError),
+    #[error("scripts: {0}")]
+    ScriptsError(#[from] ScriptsError),
+    #[error("{0} check(s) of the scripts file failed")]
+    ScriptsCheckFailed(usize),
+    #[error("'{0}' already exists, use --force to overwrite it")]
+    ConfigFileExists(PathBuf),
 }
 
 /// `WACommand` (`W`ithout `A`pplication) is command that doesn't need `App` for being executed.
@@ -52,3 +65,109 @@ impl WACommand for UpdateWAPCMD {
         Ok(())
     }
 }
+
+pub struct ScriptsCheckWACMD;
+
+impl ScriptsCheckWACMD {
+    pub fn new() -> Self {
+        ScriptsCheckWACMD
+    }
+}
+
+impl WACommand for ScriptsCheckWACMD {
+    fn execute(&self) -> Result<(), WAExexutionError> {
+        let config = read_config()?;
+        let scripts = open_scripts(&config)?;
+        scripts.set_library(LuaLibrary::new(config));
+
+        let context = Context::auto().unwrap_or(Context::new(80, 24));
+        let checks = scripts.check(context);
+
+        let mut failed = 0;
+        for (name, result) in checks {
+            match result {
+                Ok(()) => println!("ok      {}", name),
+                Err(err) => {
+                    failed += 1;
+                    println!("failed  {}: {}", name, err);
+                }
+            }
+        }
+
+        if failed > 0 {
+            return Err(WAExexutionError::ScriptsCheckFailed(failed));
+        }
+
+        println!("The scripts file is valid");
+
+        Ok(())
+    }
+}
+
+/// Runs the command of the scripts file called by its name instead of a subcommand of popusk.
+/// If the scripts file doesn't define `commands.<name>`, the name is reported as an unknown
+/// subcommand. The library is opened only if the command uses it.
+pub struct ExternalWACMD {
+    name: String,
+    args: Vec<String>,
+}
+
+impl ExternalWACMD {
+    pub fn new(name: String, args: Vec<String>) -> Self {
+        ExternalWACMD { name, args }
+    }
+}
+
+impl WACommand for ExternalWACMD {
+    fn execute(&self) -> Result<(), WAExexutionError> {
+        let config = read_config()?;
+        let scripts = open_scripts(&config)?;
+        scripts.set_library(LuaLibrary::new(config));
+
+        if !scripts.has_command(&self.name)? {
+            exit_unknown_subcommand(&self.name);
+        }
+        if let Some(output) = scripts.run_command(&self.name, self.args.clone())? {
+            println!("{}", output.trim_end());
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the default config and scripts file to the config directory.
+pub struct ConfigInitWACMD {
+    force: bool,
+}
+
+impl ConfigInitWACMD {
+    pub fn new(force: bool) -> Self {
+        ConfigInitWACMD { force }
+    }
+}
+
+impl WACommand for ConfigInitWACMD {
+    fn execute(&self) -> Result<(), WAExexutionError> {
+        let files = [
+            (configfile()?, DEFAULT_CONFIG),
+            (scriptfile()?, DEFAULT_SCRIPTS),
+        ];
+
+        if !self.force {
+            if let Some((path, _)) = files.iter().find(|(path, _)| path.exists()) {
+                return Err(WAExexutionError::ConfigFileExists(path.clone()));
+            }
+        }
+
+        for (path, content) in files {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(&path, content)?;
+
+            println!("'{}' was written", path.to_string_lossy());
+        }
+
+        Ok(())
+    }
+}